@@ -0,0 +1,216 @@
+type Point = (f32, f32);
+
+/// Trade-off between speed and how close the matching gets to the minimum
+/// total travel distance.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AssignmentMethod {
+    /// Match points with the same rank along a Hilbert curve. O(n log n),
+    /// keeps neighbours together but leaves some long or crossing paths.
+    Hilbert,
+    /// Hilbert matching followed by `passes` rounds of pairwise swaps between
+    /// nearby particles whenever swapping shortens the total distance, which
+    /// also removes most crossings.
+    Refined { passes: usize, window: usize },
+    /// Bertsekas' auction algorithm with epsilon scaling. Gets within
+    /// `n * epsilon` of the optimal total distance; O(n^2) per round, so best
+    /// kept to a few thousand points.
+    Auction { epsilon: f32 },
+}
+
+impl Default for AssignmentMethod {
+    fn default() -> Self {
+        AssignmentMethod::Refined {
+            passes: 4,
+            window: 8,
+        }
+    }
+}
+
+/// For each source point, picks the index of the target it should travel to.
+/// When there are more sources than targets, targets are shared between
+/// neighbouring sources; when there are fewer, the spare targets are unused.
+pub fn assign(sources: &[Point], targets: &[Point], method: AssignmentMethod) -> Vec<usize> {
+    let n = sources.len();
+    if n == 0 || targets.is_empty() {
+        return Vec::new();
+    }
+
+    let (min, max) = bounds(sources.iter().chain(targets));
+    let source_order = hilbert_order(sources, min, max);
+    let target_order = hilbert_order(targets, min, max);
+
+    // Equal-rank matching along the curve; with unequal counts this spreads
+    // the targets evenly over the sources.
+    let m = target_order.len();
+    let mut assignment = vec![0; n];
+    for (rank, &src) in source_order.iter().enumerate() {
+        assignment[src] = target_order[rank * m / n];
+    }
+
+    match method {
+        AssignmentMethod::Hilbert => {}
+        AssignmentMethod::Refined { passes, window } => {
+            refine(
+                sources,
+                targets,
+                &source_order,
+                &mut assignment,
+                passes,
+                window,
+            );
+        }
+        AssignmentMethod::Auction { epsilon } => {
+            // The auction needs a square problem, so it works on the targets
+            // picked above (with repeats) and only permutes them.
+            let slots: Vec<usize> = source_order.iter().map(|&src| assignment[src]).collect();
+            let owners = auction(sources, targets, &slots, epsilon);
+            for (slot, &src) in owners.iter().enumerate() {
+                assignment[src] = slots[slot];
+            }
+        }
+    }
+    assignment
+}
+
+/// Returns `targets` reordered so that entry `i` is the destination of
+/// `sources[i]`.
+pub fn assign_points(sources: &[Point], targets: &[Point], method: AssignmentMethod) -> Vec<Point> {
+    assign(sources, targets, method)
+        .into_iter()
+        .map(|idx| targets[idx])
+        .collect()
+}
+
+/// Sum of straight-line distances travelled under `assignment`.
+pub fn total_distance(sources: &[Point], targets: &[Point], assignment: &[usize]) -> f32 {
+    sources
+        .iter()
+        .zip(assignment)
+        .map(|(&s, &t)| distance(s, targets[t]))
+        .sum()
+}
+
+fn refine(
+    sources: &[Point],
+    targets: &[Point],
+    order: &[usize],
+    assignment: &mut [usize],
+    passes: usize,
+    window: usize,
+) {
+    for _ in 0..passes {
+        let mut improved = false;
+        for a in 0..order.len() {
+            for b in a + 1..(a + 1 + window).min(order.len()) {
+                let (i, j) = (order[a], order[b]);
+                let (ti, tj) = (targets[assignment[i]], targets[assignment[j]]);
+                let current = distance(sources[i], ti) + distance(sources[j], tj);
+                let swapped = distance(sources[i], tj) + distance(sources[j], ti);
+                if swapped + 1e-4 < current {
+                    assignment.swap(i, j);
+                    improved = true;
+                }
+            }
+        }
+        if !improved {
+            break;
+        }
+    }
+}
+
+/// Assigns each slot (a target index) to one source, minimizing total
+/// distance. Returns the owning source of every slot.
+fn auction(sources: &[Point], targets: &[Point], slots: &[usize], epsilon: f32) -> Vec<usize> {
+    let n = sources.len();
+    let benefit = |i: usize, s: usize| -distance(sources[i], targets[slots[s]]);
+    let (min, max) = bounds(sources.iter().chain(targets));
+    let max_cost = distance(min, max).max(1.0);
+
+    // Epsilon scaling: cheap coarse rounds first, each one leaving prices
+    // that make the next, finer round converge quickly.
+    let final_epsilon = epsilon.max(1e-6);
+    let mut prices = vec![0.0f32; n];
+    let mut eps = (max_cost / 4.0).max(final_epsilon);
+    loop {
+        let mut owner: Vec<Option<usize>> = vec![None; n];
+        let mut unassigned: Vec<usize> = (0..n).rev().collect();
+        while let Some(i) = unassigned.pop() {
+            let (mut best, mut best_value, mut second_value) =
+                (0, f32::NEG_INFINITY, f32::NEG_INFINITY);
+            for (s, price) in prices.iter().enumerate() {
+                let value = benefit(i, s) - price;
+                if value > best_value {
+                    second_value = best_value;
+                    best_value = value;
+                    best = s;
+                } else if value > second_value {
+                    second_value = value;
+                }
+            }
+            let increment = if second_value.is_finite() {
+                best_value - second_value
+            } else {
+                0.0
+            };
+            prices[best] += increment + eps;
+            if let Some(previous) = owner[best].replace(i) {
+                unassigned.push(previous);
+            }
+        }
+
+        if eps <= final_epsilon {
+            return owner
+                .into_iter()
+                .map(|o| o.expect("every slot is bid on"))
+                .collect();
+        }
+        eps = (eps / 4.0).max(final_epsilon);
+    }
+}
+
+fn hilbert_order(points: &[Point], min: Point, max: Point) -> Vec<usize> {
+    let span = (max.0 - min.0).max(max.1 - min.1).max(f32::EPSILON);
+    let side = (1u32 << 16) - 1;
+    let mut keyed: Vec<(u64, usize)> = points
+        .iter()
+        .enumerate()
+        .map(|(idx, &(x, y))| {
+            let hx = ((x - min.0) / span * side as f32) as u32;
+            let hy = ((y - min.1) / span * side as f32) as u32;
+            (hilbert_index(hx, hy), idx)
+        })
+        .collect();
+    keyed.sort_unstable();
+    keyed.into_iter().map(|(_, idx)| idx).collect()
+}
+
+/// Position of `(x, y)` along a 2^16 x 2^16 Hilbert curve.
+fn hilbert_index(mut x: u32, mut y: u32) -> u64 {
+    let mut d = 0u64;
+    let mut s = 1u32 << 15;
+    while s > 0 {
+        let rx = u32::from(x & s > 0);
+        let ry = u32::from(y & s > 0);
+        d += u64::from(s) * u64::from(s) * u64::from((3 * rx) ^ ry);
+        if ry == 0 {
+            if rx == 1 {
+                x = 0xFFFF - x;
+                y = 0xFFFF - y;
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+        s >>= 1;
+    }
+    d
+}
+
+fn bounds<'a>(points: impl Iterator<Item = &'a Point>) -> (Point, Point) {
+    points.fold(
+        ((f32::MAX, f32::MAX), (f32::MIN, f32::MIN)),
+        |(min, max), &(x, y)| ((min.0.min(x), min.1.min(y)), (max.0.max(x), max.1.max(y))),
+    )
+}
+
+fn distance(a: Point, b: Point) -> f32 {
+    ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+}
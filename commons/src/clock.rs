@@ -0,0 +1,137 @@
+use std::time::Instant;
+
+/// Where a `Clock` gets the seconds that pass each tick.
+#[derive(Clone, Copy, Debug)]
+pub enum Source {
+    /// The wall clock, as measured between ticks.
+    RealTime { last_tick: Option<Instant> },
+    /// Exactly this many seconds per tick, whatever the wall clock says.
+    Fixed(f32),
+    /// Only `Clock::advance` moves the clock; ticks add nothing.
+    Manual,
+}
+
+/// Animation time. Every tick the clock reads how much time passed from its
+/// `Source`, scales it by `speed` (or drops it while paused), and adds it to
+/// `time`. Code that animates should read `time` and `dt` from here rather
+/// than from the wall clock, so a fixed-step or manual clock replays it
+/// exactly.
+#[derive(Clone, Debug)]
+pub struct Clock {
+    pub source: Source,
+    /// Kept in f64 so thousands of small steps add up exactly enough.
+    time: f64,
+    dt: f32,
+    speed: f32,
+    paused: bool,
+    seeks: u32,
+}
+
+impl Clock {
+    pub fn new(source: Source) -> Self {
+        Self {
+            source,
+            time: 0.0,
+            dt: 0.0,
+            speed: 1.0,
+            paused: false,
+            seeks: 0,
+        }
+    }
+
+    /// Follows the wall clock. The first tick is zero seconds long.
+    pub fn real_time() -> Self {
+        Self::new(Source::RealTime { last_tick: None })
+    }
+
+    /// Moves exactly `step` seconds per tick.
+    pub fn fixed(step: f32) -> Self {
+        Self::new(Source::Fixed(step))
+    }
+
+    /// Moves only when `advance` is called.
+    pub fn manual() -> Self {
+        Self::new(Source::Manual)
+    }
+
+    /// Starts a new frame, advancing by whatever the source says has passed
+    /// since the last one. Returns the new `dt`.
+    pub fn tick(&mut self) -> f32 {
+        let elapsed = match &mut self.source {
+            Source::RealTime { last_tick } => {
+                let now = Instant::now();
+                let elapsed = last_tick.map_or(0.0, |last| (now - last).as_secs_f32());
+                *last_tick = Some(now);
+                elapsed
+            }
+            Source::Fixed(step) => *step,
+            Source::Manual => 0.0,
+        };
+        self.advance(elapsed)
+    }
+
+    /// Starts a new frame `seconds` after the last, before scaling by
+    /// `speed`. Returns the new `dt`.
+    pub fn advance(&mut self, seconds: f32) -> f32 {
+        self.dt = if self.paused {
+            0.0
+        } else {
+            seconds.max(0.0) * self.speed
+        };
+        self.time += self.dt as f64;
+        self.dt
+    }
+
+    /// Seconds of animation so far.
+    pub fn time(&self) -> f32 {
+        self.time as f32
+    }
+
+    /// Seconds of animation in the last tick; zero while paused.
+    pub fn dt(&self) -> f32 {
+        self.dt
+    }
+
+    /// Jumps to `time` seconds. The next tick carries on from there.
+    pub fn seek(&mut self, time: f32) {
+        self.time = time.max(0.0) as f64;
+        self.seeks += 1;
+    }
+
+    /// How many times the clock has been seeked, so code following it can
+    /// tell a jump from a long frame.
+    pub fn seeks(&self) -> u32 {
+        self.seeks
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// Plays at `speed` times the source's rate; 2 is double speed, 0.5
+    /// slow motion. The clock never runs backwards, so negative speeds
+    /// count as 0.
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed.max(0.0);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Stops or restarts time. A paused real-time clock still tracks the
+    /// wall clock, so resuming doesn't jump ahead by the time spent paused.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self::real_time()
+    }
+}
@@ -0,0 +1,327 @@
+use std::f32::consts::PI;
+
+/// Robert Penner's easing curves, each usable as `In`, `Out` or `InOut`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Curve {
+    Quad,
+    Cubic,
+    Quart,
+    Quint,
+    Sine,
+    Expo,
+    Circ,
+    /// Pulls back slightly before moving (overshoots below 0 or above 1).
+    Back,
+    /// Springs past the end and oscillates (overshoots).
+    Elastic,
+    Bounce,
+}
+
+impl Curve {
+    const ALL: [Curve; 10] = [
+        Curve::Quad,
+        Curve::Cubic,
+        Curve::Quart,
+        Curve::Quint,
+        Curve::Sine,
+        Curve::Expo,
+        Curve::Circ,
+        Curve::Back,
+        Curve::Elastic,
+        Curve::Bounce,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Curve::Quad => "quad",
+            Curve::Cubic => "cubic",
+            Curve::Quart => "quart",
+            Curve::Quint => "quint",
+            Curve::Sine => "sine",
+            Curve::Expo => "expo",
+            Curve::Circ => "circ",
+            Curve::Back => "back",
+            Curve::Elastic => "elastic",
+            Curve::Bounce => "bounce",
+        }
+    }
+
+    fn ease_in(self, t: f32) -> f32 {
+        match self {
+            Curve::Quad => t * t,
+            Curve::Cubic => t * t * t,
+            Curve::Quart => t * t * t * t,
+            Curve::Quint => t * t * t * t * t,
+            Curve::Sine => 1.0 - (t * PI / 2.0).cos(),
+            Curve::Expo if t == 0.0 => 0.0,
+            Curve::Expo => 2.0f32.powf(10.0 * t - 10.0),
+            Curve::Circ => 1.0 - (1.0 - t * t).max(0.0).sqrt(),
+            Curve::Back => BACK_OVERSHOOT_IN * t * t * t - BACK_OVERSHOOT * t * t,
+            Curve::Elastic if t == 0.0 || t == 1.0 => t,
+            Curve::Elastic => {
+                -(2.0f32.powf(10.0 * t - 10.0)) * ((10.0 * t - 10.75) * (2.0 * PI / 3.0)).sin()
+            }
+            Curve::Bounce => 1.0 - bounce_out(1.0 - t),
+        }
+    }
+
+    fn ease_out(self, t: f32) -> f32 {
+        match self {
+            Curve::Bounce => bounce_out(t),
+            _ => 1.0 - self.ease_in(1.0 - t),
+        }
+    }
+
+    fn ease_in_out(self, t: f32) -> f32 {
+        match self {
+            // Penner's in-out back and elastic use their own constants rather
+            // than joining the in and out halves.
+            Curve::Back => {
+                let c = BACK_OVERSHOOT * 1.525;
+                let u = 2.0 * t;
+                if t < 0.5 {
+                    u * u * ((c + 1.0) * u - c) / 2.0
+                } else {
+                    let u = u - 2.0;
+                    (u * u * ((c + 1.0) * u + c) + 2.0) / 2.0
+                }
+            }
+            Curve::Elastic if t == 0.0 || t == 1.0 => t,
+            Curve::Elastic => {
+                let wave = ((20.0 * t - 11.125) * (2.0 * PI / 4.5)).sin();
+                if t < 0.5 {
+                    -(2.0f32.powf(20.0 * t - 10.0) * wave) / 2.0
+                } else {
+                    2.0f32.powf(-20.0 * t + 10.0) * wave / 2.0 + 1.0
+                }
+            }
+            _ if t < 0.5 => self.ease_in(2.0 * t) / 2.0,
+            _ => 1.0 - self.ease_in(2.0 - 2.0 * t) / 2.0,
+        }
+    }
+}
+
+const BACK_OVERSHOOT: f32 = 1.70158;
+const BACK_OVERSHOOT_IN: f32 = BACK_OVERSHOOT + 1.0;
+
+fn bounce_out(t: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+    if t < 1.0 / D {
+        N * t * t
+    } else if t < 2.0 / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984375
+    }
+}
+
+/// Maps linear progress through a transition to eased progress. Written in
+/// scripts by name, e.g. `"expo-in-out"`, `"cubic-bezier(0.4, 0, 0.2, 1)"`
+/// or `"steps(4)"`; see `FromStr`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Easing {
+    #[default]
+    Linear,
+    /// Hermite smoothstep, `3t² - 2t³`.
+    Smoothstep,
+    /// Starts slow.
+    In(Curve),
+    /// Ends slow.
+    Out(Curve),
+    /// Starts and ends slow.
+    InOut(Curve),
+    /// CSS `cubic-bezier(x1, y1, x2, y2)`: the curve from (0, 0) to (1, 1)
+    /// with those control points. `x1` and `x2` are clamped to 0..=1 so the
+    /// curve stays a function of time.
+    CubicBezier(f32, f32, f32, f32),
+    /// CSS `steps(n)`: jumps in `n` equal steps, each at the end of its
+    /// interval.
+    Steps(u32),
+}
+
+impl Easing {
+    /// CSS `ease`.
+    pub const EASE: Easing = Easing::CubicBezier(0.25, 0.1, 0.25, 1.0);
+    /// CSS `ease-in`.
+    pub const EASE_IN: Easing = Easing::CubicBezier(0.42, 0.0, 1.0, 1.0);
+    /// CSS `ease-out`.
+    pub const EASE_OUT: Easing = Easing::CubicBezier(0.0, 0.0, 0.58, 1.0);
+    /// CSS `ease-in-out`.
+    pub const EASE_IN_OUT: Easing = Easing::CubicBezier(0.42, 0.0, 0.58, 1.0);
+
+    /// Every named easing: linear, smoothstep and each curve in, out and
+    /// in-out.
+    pub fn named() -> impl Iterator<Item = Easing> {
+        [Easing::Linear, Easing::Smoothstep].into_iter().chain(
+            Curve::ALL
+                .into_iter()
+                .flat_map(|c| [Easing::In(c), Easing::Out(c), Easing::InOut(c)]),
+        )
+    }
+
+    /// Eases `t`, which is clamped to 0..=1. Every easing maps 0 to 0 and 1
+    /// to 1; back and elastic curves overshoot in between.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::Smoothstep => t * t * (3.0 - 2.0 * t),
+            Easing::In(curve) => curve.ease_in(t),
+            Easing::Out(curve) => curve.ease_out(t),
+            Easing::InOut(curve) => curve.ease_in_out(t),
+            Easing::CubicBezier(x1, y1, x2, y2) => {
+                cubic_bezier(x1.clamp(0.0, 1.0), y1, x2.clamp(0.0, 1.0), y2, t)
+            }
+            Easing::Steps(n) => {
+                let n = n.max(1) as f32;
+                (t * n).floor() / n
+            }
+        }
+    }
+}
+
+/// One coordinate of a cubic Bézier from 0 to 1 with control values `a`, `b`.
+fn bezier(a: f32, b: f32, s: f32) -> f32 {
+    let r = 1.0 - s;
+    3.0 * r * r * s * a + 3.0 * r * s * s * b + s * s * s
+}
+
+fn bezier_slope(a: f32, b: f32, s: f32) -> f32 {
+    let r = 1.0 - s;
+    3.0 * r * r * a + 6.0 * r * s * (b - a) + 3.0 * s * s * (1.0 - b)
+}
+
+/// Solves the curve's x for `t` and returns its y there: a few Newton steps,
+/// falling back to bisection where the slope is too flat.
+fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, t: f32) -> f32 {
+    let mut s = t;
+    for _ in 0..8 {
+        let err = bezier(x1, x2, s) - t;
+        if err.abs() < 1e-6 {
+            return bezier(y1, y2, s);
+        }
+        let slope = bezier_slope(x1, x2, s);
+        if slope.abs() < 1e-6 {
+            break;
+        }
+        s = (s - err / slope).clamp(0.0, 1.0);
+    }
+    let (mut lo, mut hi) = (0.0f32, 1.0f32);
+    s = t;
+    for _ in 0..32 {
+        let x = bezier(x1, x2, s);
+        if (x - t).abs() < 1e-6 {
+            break;
+        }
+        if x < t {
+            lo = s;
+        } else {
+            hi = s;
+        }
+        s = (lo + hi) / 2.0;
+    }
+    bezier(y1, y2, s)
+}
+
+/// Text that `Easing::from_str` does not recognize.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseEasingError(pub String);
+
+impl std::fmt::Display for ParseEasingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown easing {:?}", self.0)
+    }
+}
+
+impl std::error::Error for ParseEasingError {}
+
+/// Parses the forms `Display` writes: `linear`, `smoothstep`, `<curve>-in`,
+/// `<curve>-out`, `<curve>-in-out` (e.g. `quad-in`, `elastic-in-out`),
+/// `cubic-bezier(x1, y1, x2, y2)` and `steps(n)`, plus the CSS keywords
+/// `ease`, `ease-in`, `ease-out` and `ease-in-out`.
+impl std::str::FromStr for Easing {
+    type Err = ParseEasingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseEasingError(s.to_string());
+        let name = s.trim().to_ascii_lowercase();
+        let call = |function: &str| {
+            name.strip_prefix(function)?
+                .trim_start()
+                .strip_prefix('(')?
+                .strip_suffix(')')
+                .map(|args| args.split(',').map(str::trim).collect::<Vec<_>>())
+        };
+
+        if let Some(args) = call("cubic-bezier") {
+            let values: Vec<f32> = args
+                .iter()
+                .map(|a| a.parse().map_err(|_| err()))
+                .collect::<Result<_, _>>()?;
+            return match values[..] {
+                [x1, y1, x2, y2] => Ok(Easing::CubicBezier(x1, y1, x2, y2)),
+                _ => Err(err()),
+            };
+        }
+        if let Some(args) = call("steps") {
+            return match args[..] {
+                [n] => n
+                    .parse()
+                    .ok()
+                    .filter(|&n| n > 0)
+                    .map(Easing::Steps)
+                    .ok_or_else(err),
+                _ => Err(err()),
+            };
+        }
+        match name.as_str() {
+            "ease" => return Ok(Easing::EASE),
+            "ease-in" => return Ok(Easing::EASE_IN),
+            "ease-out" => return Ok(Easing::EASE_OUT),
+            "ease-in-out" => return Ok(Easing::EASE_IN_OUT),
+            _ => {}
+        }
+        Easing::named()
+            .find(|e| e.to_string() == name)
+            .ok_or_else(err)
+    }
+}
+
+impl std::fmt::Display for Easing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Easing::Linear => f.write_str("linear"),
+            Easing::Smoothstep => f.write_str("smoothstep"),
+            Easing::In(curve) => write!(f, "{}-in", curve.name()),
+            Easing::Out(curve) => write!(f, "{}-out", curve.name()),
+            Easing::InOut(curve) => write!(f, "{}-in-out", curve.name()),
+            Easing::CubicBezier(x1, y1, x2, y2) => {
+                write!(f, "cubic-bezier({x1}, {y1}, {x2}, {y2})")
+            }
+            Easing::Steps(n) => write!(f, "steps({n})"),
+        }
+    }
+}
+
+/// Serialized as its `Display` text.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Easing {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Easing {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
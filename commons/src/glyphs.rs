@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::{BDFFont, BDFGlyph, bdf_glyph_runs, bdf_to_outlines};
+
+/// Glyph bounding box in font pixels, as given by the glyph's `BBX`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GlyphBounds {
+    pub width: usize,
+    pub height: usize,
+    pub x_offset: isize,
+    pub y_offset: isize,
+}
+
+#[derive(Clone, Debug)]
+pub struct Glyph {
+    /// Code in the font's own charset, as given by `ENCODING`.
+    pub encoding: u32,
+    /// The Unicode character this glyph draws, if the charset maps it.
+    pub unicode: Option<char>,
+    /// Horizontal advance (`DWIDTH` x) in font pixels.
+    pub advance: isize,
+    pub bounds: GlyphBounds,
+    /// Horizontal run segments, top-down with the baseline at `ascent`.
+    /// Each run covers the pixel row from its `y` to `y + 1`.
+    pub paths: Vec<Vec<(f32, f32)>>,
+    ascent: isize,
+    source: BDFGlyph,
+    outlines: OnceLock<Vec<Vec<(f32, f32)>>>,
+}
+
+impl Glyph {
+    /// Closed outlines from `bdf_to_outlines`, in the same top-down space as
+    /// `paths` (the y flip makes outer contours clockwise). Traced on first
+    /// use, since only outline sampling needs them.
+    pub fn outlines(&self) -> &[Vec<(f32, f32)>] {
+        self.outlines.get_or_init(|| {
+            bdf_to_outlines(&self.source)
+                .into_iter()
+                .map(|outline| {
+                    outline
+                        .into_iter()
+                        .map(|(x, y)| (x, self.ascent as f32 - y))
+                        .collect()
+                })
+                .collect()
+        })
+    }
+}
+
+/// Glyphs of a font indexed by Unicode `char` for constant-time lookup.
+#[derive(Clone, Debug)]
+pub struct GlyphSet {
+    pub ascent: isize,
+    pub descent: isize,
+    glyphs: Vec<Glyph>,
+    index: HashMap<char, usize>,
+    default: Option<usize>,
+}
+
+impl GlyphSet {
+    pub fn from_font(font: &BDFFont) -> Self {
+        let ascent = font.properties.font_ascent() as isize;
+        let descent = font.properties.font_descent() as isize;
+        let charset = font.charset();
+
+        let mut glyphs = Vec::with_capacity(font.glyphs.len());
+        let mut index = HashMap::with_capacity(font.glyphs.len());
+        for glyph in &font.glyphs {
+            let Some(encoding) = glyph.encoding else {
+                continue;
+            };
+            let (width, height, x_offset, y_offset) = glyph.bbx;
+            let unicode = charset.to_unicode(encoding);
+            if let Some(c) = unicode {
+                index.entry(c).or_insert(glyphs.len());
+            }
+            glyphs.push(Glyph {
+                encoding,
+                unicode,
+                advance: glyph.dwidth.0,
+                bounds: GlyphBounds {
+                    width,
+                    height,
+                    x_offset,
+                    y_offset,
+                },
+                paths: bdf_glyph_runs(glyph, ascent),
+                ascent,
+                source: glyph.clone(),
+                outlines: OnceLock::new(),
+            });
+        }
+
+        // DEFAULT_CHAR is a code in the font's charset, not Unicode.
+        let default = font
+            .properties
+            .get_int("DEFAULT_CHAR")
+            .and_then(|code| glyphs.iter().position(|g| g.encoding as i64 == code));
+
+        Self {
+            ascent,
+            descent,
+            glyphs,
+            index,
+            default,
+        }
+    }
+
+    /// Looks up `c`, falling back to the font's `DEFAULT_CHAR` glyph.
+    pub fn get(&self, c: char) -> Option<&Glyph> {
+        self.index
+            .get(&c)
+            .or(self.default.as_ref())
+            .map(|&idx| &self.glyphs[idx])
+    }
+
+    /// Looks up `c` without the `DEFAULT_CHAR` fallback.
+    pub fn get_exact(&self, c: char) -> Option<&Glyph> {
+        self.index.get(&c).map(|&idx| &self.glyphs[idx])
+    }
+
+    pub fn default_glyph(&self) -> Option<&Glyph> {
+        self.default.map(|idx| &self.glyphs[idx])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Glyph> {
+        self.glyphs.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.glyphs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.glyphs.is_empty()
+    }
+}
@@ -0,0 +1,217 @@
+use crate::GlyphSet;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+    /// Stretch word gaps to fill the line; the last line of a paragraph is
+    /// left-aligned.
+    Justify,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerticalAlign {
+    Top,
+    Middle,
+    Bottom,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct LayoutOptions {
+    /// Maximum line width in output pixels; `None` wraps at the box width.
+    pub wrap_width: Option<f32>,
+    pub align: Align,
+    pub vertical_align: VerticalAlign,
+    /// Multiplier on `FONT_ASCENT + FONT_DESCENT`.
+    pub line_spacing: f32,
+    /// Extra space after each glyph, in font pixels.
+    pub letter_spacing: f32,
+    pub scale: f32,
+    /// Round glyph origins to whole output pixels, which keeps integer
+    /// scales crisp.
+    pub pixel_snap: bool,
+}
+
+impl Default for LayoutOptions {
+    fn default() -> Self {
+        Self {
+            wrap_width: None,
+            align: Align::Left,
+            vertical_align: VerticalAlign::Top,
+            line_spacing: 1.0,
+            letter_spacing: 0.0,
+            scale: 1.0,
+            pixel_snap: false,
+        }
+    }
+}
+
+/// A glyph placed in the box. `x`/`y` is the top-left of its line box, in the
+/// same top-down space as `Glyph::paths`, so a path point `p` lands at
+/// `(x + p.0 * scale, y + p.1 * scale)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PositionedGlyph {
+    pub ch: char,
+    pub x: f32,
+    pub y: f32,
+    pub scale: f32,
+    pub line: usize,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LayoutBounds {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TextLayout {
+    pub glyphs: Vec<PositionedGlyph>,
+    /// Union of the line boxes.
+    pub bounds: LayoutBounds,
+    pub line_count: usize,
+}
+
+struct Line {
+    words: Vec<Vec<char>>,
+    /// Ends a paragraph (or the text), so it is never justified.
+    last_in_paragraph: bool,
+}
+
+/// Lays out `text` inside a box of `box_size` output pixels, wrapping on
+/// spaces, breaking words that don't fit on a line of their own, and
+/// honouring explicit `\n`.
+pub fn layout_text(
+    text: &str,
+    glyphs: &GlyphSet,
+    box_size: (f32, f32),
+    options: &LayoutOptions,
+) -> TextLayout {
+    let scale = options.scale;
+    let advance = |c: char| {
+        glyphs
+            .get(c)
+            .map_or(0.0, |g| (g.advance as f32 + options.letter_spacing) * scale)
+    };
+    let word_width = |word: &[char]| word.iter().map(|&c| advance(c)).sum::<f32>();
+    let space = advance(' ');
+    let wrap_width = options.wrap_width.unwrap_or(box_size.0);
+
+    let mut lines: Vec<Line> = Vec::new();
+    for paragraph in text.split('\n') {
+        let mut current: Vec<Vec<char>> = Vec::new();
+        let mut current_width = 0.0;
+        for word in paragraph.split(' ').filter(|w| !w.is_empty()) {
+            let mut word: Vec<char> = word.chars().collect();
+            loop {
+                let width = word_width(&word);
+                let needed = if current.is_empty() {
+                    width
+                } else {
+                    current_width + space + width
+                };
+                if needed <= wrap_width || (current.is_empty() && word.len() <= 1) {
+                    current_width = needed;
+                    current.push(word);
+                    break;
+                }
+                if !current.is_empty() {
+                    lines.push(Line {
+                        words: std::mem::take(&mut current),
+                        last_in_paragraph: false,
+                    });
+                    current_width = 0.0;
+                    continue;
+                }
+                // The word alone is too wide: put as much as fits on this line.
+                let mut split = 1;
+                let mut width = advance(word[0]);
+                while split < word.len() && width + advance(word[split]) <= wrap_width {
+                    width += advance(word[split]);
+                    split += 1;
+                }
+                let rest = word.split_off(split);
+                lines.push(Line {
+                    words: vec![word],
+                    last_in_paragraph: false,
+                });
+                word = rest;
+            }
+        }
+        lines.push(Line {
+            words: current,
+            last_in_paragraph: true,
+        });
+    }
+
+    let line_height = (glyphs.ascent + glyphs.descent) as f32 * scale * options.line_spacing;
+    let block_height = line_height * lines.len() as f32;
+    let top = match options.vertical_align {
+        VerticalAlign::Top => 0.0,
+        VerticalAlign::Middle => (box_size.1 - block_height) / 2.0,
+        VerticalAlign::Bottom => box_size.1 - block_height,
+    };
+
+    let mut layout = TextLayout {
+        line_count: lines.len(),
+        ..Default::default()
+    };
+    let (mut min_x, mut max_x) = (f32::INFINITY, f32::NEG_INFINITY);
+    for (line_idx, line) in lines.iter().enumerate() {
+        let words_width: f32 = line.words.iter().map(|w| word_width(w)).sum();
+        let gaps = line.words.len().saturating_sub(1);
+        let natural = words_width + space * gaps as f32;
+
+        let justify = options.align == Align::Justify && !line.last_in_paragraph && gaps > 0;
+        let gap = if justify {
+            (wrap_width - words_width) / gaps as f32
+        } else {
+            space
+        };
+        let width = if justify { wrap_width } else { natural };
+        let mut x = match options.align {
+            Align::Left | Align::Justify => 0.0,
+            Align::Center => (box_size.0 - width) / 2.0,
+            Align::Right => box_size.0 - width,
+        };
+        let y = top + line_height * line_idx as f32;
+
+        min_x = min_x.min(x);
+        max_x = max_x.max(x + width);
+        for (word_idx, word) in line.words.iter().enumerate() {
+            if word_idx > 0 {
+                x += gap;
+            }
+            for &ch in word {
+                let (gx, gy) = if options.pixel_snap {
+                    (x.round(), y.round())
+                } else {
+                    (x, y)
+                };
+                if glyphs.get(ch).is_some() {
+                    layout.glyphs.push(PositionedGlyph {
+                        ch,
+                        x: gx,
+                        y: gy,
+                        scale,
+                        line: line_idx,
+                    });
+                }
+                x += advance(ch);
+            }
+        }
+    }
+
+    if min_x.is_finite() {
+        layout.bounds = LayoutBounds {
+            x: min_x,
+            y: top,
+            width: max_x - min_x,
+            height: block_height,
+        };
+    }
+    layout
+}
@@ -1,34 +1,193 @@
-#[derive(Clone)]
+pub mod assignment;
+mod charset;
+pub mod clock;
+pub mod easing;
+mod glyphs;
+pub mod layout;
+mod outline;
+pub mod physics;
+pub mod sampling;
+pub mod timeline;
+mod vectorize;
+
+pub use charset::Charset;
+pub use glyphs::{Glyph, GlyphBounds, GlyphSet};
+pub use outline::bdf_to_outlines;
+pub use vectorize::{
+    BezierContour, PathSegment, VectorizeOptions, flatten_contour, vectorize_glyph,
+};
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct BDFGlyph {
     pub startchar: String,
-    pub encoding: usize,
-    pub swidth: (usize, usize),
-    pub dwidth: (usize, usize),
+    /// Standard encoding, or `None` for `ENCODING -1` (unencoded glyphs).
+    pub encoding: Option<u32>,
+    /// Non-standard index given as `ENCODING -1 <n>`.
+    pub alt_encoding: Option<u32>,
+    pub swidth: (isize, isize),
+    pub dwidth: (isize, isize),
+    /// Vertical writing metrics (`SWIDTH1`, `DWIDTH1`, `VVECTOR`), if present.
+    pub swidth1: Option<(isize, isize)>,
+    pub dwidth1: Option<(isize, isize)>,
+    pub vvector: Option<(isize, isize)>,
     pub bbx: (usize, usize, isize, isize),
-    pub bitmap: Vec<u16>,
+    /// Packed rows, most significant bit first, `row_bytes()` bytes per row.
+    pub bitmap: Vec<u8>,
+}
+
+impl BDFGlyph {
+    /// Number of bytes used to store one bitmap row, from the BBX width.
+    pub fn row_bytes(&self) -> usize {
+        self.bbx.0.div_ceil(8)
+    }
+
+    pub fn row(&self, row: usize) -> &[u8] {
+        let stride = self.row_bytes();
+        self.bitmap
+            .get(row * stride..(row + 1) * stride)
+            .unwrap_or(&[])
+    }
+
+    /// Whether the pixel at `col`, `row` (top-down, within the BBX) is set.
+    pub fn pixel(&self, col: usize, row: usize) -> bool {
+        col < self.bbx.0
+            && self
+                .row(row)
+                .get(col / 8)
+                .is_some_and(|byte| byte & (0x80 >> (col % 8)) != 0)
+    }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum BDFPropertyValue {
+    Int(i64),
+    /// String value with the surrounding quotes removed and `""` unescaped.
+    Str(String),
+}
+
+/// The `STARTPROPERTIES` table, in file order.
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct BDFProperties {
-    pub point_size: usize,
-    pub pixel_size: usize,
-    pub resolution_x: usize,
-    pub resolution_y: usize,
-    pub font_ascent: usize,
-    pub font_descent: usize,
-    pub average_width: usize,
-    pub spacing: String,
-    pub gbdfed_info: String,
-    pub charset_encoding: String,
-    pub charset_registry: String,
-    pub family_name: String,
-    pub foundry: String,
-    pub setwidth_name: String,
-    pub slant: String,
-    pub weight_name: String,
+    pub entries: Vec<(String, BDFPropertyValue)>,
 }
 
-#[derive(Clone)]
+impl BDFProperties {
+    pub fn get(&self, name: &str) -> Option<&BDFPropertyValue> {
+        self.entries
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value)
+    }
+
+    pub fn get_int(&self, name: &str) -> Option<i64> {
+        match self.get(name)? {
+            BDFPropertyValue::Int(value) => Some(*value),
+            BDFPropertyValue::Str(_) => None,
+        }
+    }
+
+    pub fn get_str(&self, name: &str) -> Option<&str> {
+        match self.get(name)? {
+            BDFPropertyValue::Str(value) => Some(value),
+            BDFPropertyValue::Int(_) => None,
+        }
+    }
+
+    /// Replaces an existing property in place, or appends a new one.
+    pub fn set(&mut self, name: &str, value: BDFPropertyValue) {
+        match self.entries.iter_mut().find(|(key, _)| key == name) {
+            Some(entry) => entry.1 = value,
+            None => self.entries.push((name.to_string(), value)),
+        }
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<BDFPropertyValue> {
+        let idx = self.entries.iter().position(|(key, _)| key == name)?;
+        Some(self.entries.remove(idx).1)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn uint(&self, name: &str) -> usize {
+        self.get_int(name).map_or(0, |v| v.max(0) as usize)
+    }
+
+    fn string(&self, name: &str) -> &str {
+        self.get_str(name).unwrap_or("")
+    }
+
+    pub fn point_size(&self) -> usize {
+        self.uint("POINT_SIZE")
+    }
+
+    pub fn pixel_size(&self) -> usize {
+        self.uint("PIXEL_SIZE")
+    }
+
+    pub fn resolution_x(&self) -> usize {
+        self.uint("RESOLUTION_X")
+    }
+
+    pub fn resolution_y(&self) -> usize {
+        self.uint("RESOLUTION_Y")
+    }
+
+    pub fn font_ascent(&self) -> usize {
+        self.uint("FONT_ASCENT")
+    }
+
+    pub fn font_descent(&self) -> usize {
+        self.uint("FONT_DESCENT")
+    }
+
+    pub fn average_width(&self) -> usize {
+        self.uint("AVERAGE_WIDTH")
+    }
+
+    pub fn spacing(&self) -> &str {
+        self.string("SPACING")
+    }
+
+    pub fn gbdfed_info(&self) -> &str {
+        self.string("_GBDFED_INFO")
+    }
+
+    pub fn charset_encoding(&self) -> &str {
+        self.string("CHARSET_ENCODING")
+    }
+
+    pub fn charset_registry(&self) -> &str {
+        self.string("CHARSET_REGISTRY")
+    }
+
+    pub fn family_name(&self) -> &str {
+        self.string("FAMILY_NAME")
+    }
+
+    pub fn foundry(&self) -> &str {
+        self.string("FOUNDRY")
+    }
+
+    pub fn setwidth_name(&self) -> &str {
+        self.string("SETWIDTH_NAME")
+    }
+
+    pub fn slant(&self) -> &str {
+        self.string("SLANT")
+    }
+
+    pub fn weight_name(&self) -> &str {
+        self.string("WEIGHT_NAME")
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct BDFFont {
     pub size: (usize, usize, usize),
     pub font: String,
@@ -38,9 +197,131 @@ pub struct BDFFont {
     pub glyphs: Vec<BDFGlyph>,
 }
 
-pub type Curves = Vec<(String, usize, Vec<Vec<(f32, f32)>>)>;
+pub type Curves = Vec<(String, isize, Vec<Vec<(f32, f32)>>)>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BdfError {
+    /// The input ended before `expected` was found.
+    UnexpectedEof { line: usize, expected: &'static str },
+    /// A keyword line had missing or unparsable fields (`SIZE`, `BBX`,
+    /// `FONTBOUNDINGBOX`, numeric properties, ...).
+    MalformedField {
+        line: usize,
+        keyword: &'static str,
+        text: String,
+    },
+    /// A `BITMAP` row was not valid hexadecimal.
+    BadBitmapRow { line: usize, text: String },
+    /// `CHARS` declared a different number of glyphs than were defined.
+    CharsMismatch {
+        line: usize,
+        declared: usize,
+        found: usize,
+    },
+}
 
-pub fn load_bdf(contents: &str) -> Result<BDFFont, Box<dyn std::error::Error>> {
+impl std::fmt::Display for BdfError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BdfError::UnexpectedEof { line, expected } => {
+                write!(
+                    f,
+                    "line {line}: unexpected end of file, expected {expected}"
+                )
+            }
+            BdfError::MalformedField {
+                line,
+                keyword,
+                text,
+            } => write!(f, "line {line}: malformed {keyword}: {text:?}"),
+            BdfError::BadBitmapRow { line, text } => {
+                write!(f, "line {line}: bad bitmap row: {text:?}")
+            }
+            BdfError::CharsMismatch {
+                line,
+                declared,
+                found,
+            } => write!(
+                f,
+                "line {line}: CHARS declared {declared} glyphs but {found} were defined"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BdfError {}
+
+/// Splits the arguments of a keyword line, requiring at least `count` fields.
+fn bdf_fields<'a>(
+    args: &'a str,
+    count: usize,
+    line: usize,
+    keyword: &'static str,
+    text: &str,
+) -> Result<Vec<&'a str>, BdfError> {
+    let parts: Vec<&str> = args.split_whitespace().collect();
+    if parts.len() < count {
+        return Err(BdfError::MalformedField {
+            line,
+            keyword,
+            text: text.to_string(),
+        });
+    }
+    Ok(parts)
+}
+
+/// Parses a `NAME value` property line into an integer or unquoted string.
+fn bdf_property(text: &str, line: usize) -> Result<(String, BDFPropertyValue), BdfError> {
+    let malformed = || BdfError::MalformedField {
+        line,
+        keyword: "property",
+        text: text.to_string(),
+    };
+    let (name, raw) = text
+        .trim()
+        .split_once(char::is_whitespace)
+        .ok_or_else(malformed)?;
+    let raw = raw.trim();
+    let value = if let Some(quoted) = raw.strip_prefix('"') {
+        let inner = quoted.strip_suffix('"').ok_or_else(malformed)?;
+        BDFPropertyValue::Str(inner.replace("\"\"", "\""))
+    } else {
+        BDFPropertyValue::Int(raw.parse().map_err(|_| malformed())?)
+    };
+    Ok((name.to_string(), value))
+}
+
+/// Parses a two-field metric line such as `DWIDTH` or `VVECTOR`.
+fn bdf_pair(
+    args: &str,
+    line: usize,
+    keyword: &'static str,
+    text: &str,
+) -> Result<(isize, isize), BdfError> {
+    let parts = bdf_fields(args, 2, line, keyword, text)?;
+    Ok((
+        bdf_num(parts[0], line, keyword, text)?,
+        bdf_num(parts[1], line, keyword, text)?,
+    ))
+}
+
+fn bdf_num<T: std::str::FromStr>(
+    field: &str,
+    line: usize,
+    keyword: &'static str,
+    text: &str,
+) -> Result<T, BdfError> {
+    field
+        .trim()
+        .parse::<T>()
+        .map_err(|_| BdfError::MalformedField {
+            line,
+            keyword,
+            text: text.to_string(),
+        })
+}
+
+pub fn load_bdf(contents: &str) -> Result<BDFFont, BdfError> {
     let lines: Vec<&str> = contents.lines().collect();
     let mut i = 0;
 
@@ -49,165 +330,309 @@ pub fn load_bdf(contents: &str) -> Result<BDFFont, Box<dyn std::error::Error>> {
         font: String::new(),
         bounding_box: (0, 0, 0, 0),
         charcount: 0,
-        properties: BDFProperties {
-            point_size: 0,
-            pixel_size: 0,
-            resolution_x: 0,
-            resolution_y: 0,
-            font_ascent: 0,
-            font_descent: 0,
-            average_width: 0,
-            spacing: String::new(),
-            gbdfed_info: String::new(),
-            charset_encoding: String::new(),
-            charset_registry: String::new(),
-            family_name: String::new(),
-            foundry: String::new(),
-            setwidth_name: String::new(),
-            slant: String::new(),
-            weight_name: String::new(),
-        },
+        properties: BDFProperties::default(),
         glyphs: Vec::new(),
     };
+    let mut chars_line = None;
 
     while i < lines.len() {
         let line = lines[i];
+        let lineno = i + 1;
         if line.starts_with("STARTFONT ") {
             i += 1;
-        } else if line.starts_with("FONT ") {
-            font.font = line[5..].to_string();
+        } else if let Some(rest) = line.strip_prefix("FONT ") {
+            font.font = rest.to_string();
             i += 1;
-        } else if line.starts_with("SIZE ") {
-            let parts: Vec<&str> = line[5..].split_whitespace().collect();
+        } else if let Some(rest) = line.strip_prefix("SIZE ") {
+            let parts = bdf_fields(rest, 3, lineno, "SIZE", line)?;
             font.size = (
-                parts[0].parse::<usize>()?,
-                parts[1].parse::<usize>()?,
-                parts[2].parse::<usize>()?,
+                bdf_num(parts[0], lineno, "SIZE", line)?,
+                bdf_num(parts[1], lineno, "SIZE", line)?,
+                bdf_num(parts[2], lineno, "SIZE", line)?,
             );
             i += 1;
-        } else if line.starts_with("FONTBOUNDINGBOX ") {
-            let parts: Vec<&str> = line[16..].split_whitespace().collect();
+        } else if let Some(rest) = line.strip_prefix("FONTBOUNDINGBOX ") {
+            let parts = bdf_fields(rest, 4, lineno, "FONTBOUNDINGBOX", line)?;
             font.bounding_box = (
-                parts[0].parse::<usize>()?,
-                parts[1].parse::<usize>()?,
-                parts[2].parse::<isize>()?,
-                parts[3].parse::<isize>()?,
+                bdf_num(parts[0], lineno, "FONTBOUNDINGBOX", line)?,
+                bdf_num(parts[1], lineno, "FONTBOUNDINGBOX", line)?,
+                bdf_num(parts[2], lineno, "FONTBOUNDINGBOX", line)?,
+                bdf_num(parts[3], lineno, "FONTBOUNDINGBOX", line)?,
             );
             i += 1;
-        } else if line.starts_with("STARTPROPERTIES ") {
-            let _num_props: usize = line[16..].parse()?;
+        } else if let Some(rest) = line.strip_prefix("STARTPROPERTIES ") {
+            let _num_props: usize = bdf_num(rest, lineno, "STARTPROPERTIES", line)?;
             i += 1;
             while i < lines.len() && !lines[i].starts_with("ENDPROPERTIES") {
                 let prop_line = lines[i];
-                if prop_line.starts_with("POINT_SIZE ") {
-                    font.properties.point_size = prop_line[11..].parse::<usize>()?;
-                } else if prop_line.starts_with("PIXEL_SIZE ") {
-                    font.properties.pixel_size = prop_line[11..].parse::<usize>()?;
-                } else if prop_line.starts_with("RESOLUTION_X ") {
-                    font.properties.resolution_x = prop_line[13..].parse::<usize>()?;
-                } else if prop_line.starts_with("RESOLUTION_Y ") {
-                    font.properties.resolution_y = prop_line[13..].parse::<usize>()?;
-                } else if prop_line.starts_with("FONT_ASCENT ") {
-                    font.properties.font_ascent = prop_line[12..].parse::<usize>()?;
-                } else if prop_line.starts_with("FONT_DESCENT ") {
-                    font.properties.font_descent = prop_line[13..].parse::<usize>()?;
-                } else if prop_line.starts_with("AVERAGE_WIDTH ") {
-                    font.properties.average_width = prop_line[14..].parse::<usize>()?;
-                } else if prop_line.starts_with("SPACING ") {
-                    font.properties.spacing = prop_line[8..].to_string();
-                } else if prop_line.starts_with("_GBDFED_INFO ") {
-                    font.properties.gbdfed_info = prop_line[14..].to_string();
-                } else if prop_line.starts_with("CHARSET_ENCODING ") {
-                    font.properties.charset_encoding = prop_line[17..].to_string();
-                } else if prop_line.starts_with("CHARSET_REGISTRY ") {
-                    font.properties.charset_registry = prop_line[17..].to_string();
-                } else if prop_line.starts_with("FAMILY_NAME ") {
-                    font.properties.family_name = prop_line[12..].to_string();
-                } else if prop_line.starts_with("FOUNDRY ") {
-                    font.properties.foundry = prop_line[8..].to_string();
-                } else if prop_line.starts_with("SETWIDTH_NAME ") {
-                    font.properties.setwidth_name = prop_line[14..].to_string();
-                } else if prop_line.starts_with("SLANT ") {
-                    font.properties.slant = prop_line[6..].to_string();
-                } else if prop_line.starts_with("WEIGHT_NAME ") {
-                    font.properties.weight_name = prop_line[12..].to_string();
+                let lineno = i + 1;
+                if !prop_line.trim().is_empty() && !prop_line.starts_with("COMMENT") {
+                    let (name, value) = bdf_property(prop_line, lineno)?;
+                    font.properties.entries.push((name, value));
                 }
                 i += 1;
             }
+            if i == lines.len() {
+                return Err(BdfError::UnexpectedEof {
+                    line: i,
+                    expected: "ENDPROPERTIES",
+                });
+            }
             i += 1;
-        } else if line.starts_with("CHARS ") {
-            font.charcount = line[6..].parse::<usize>()?;
+        } else if let Some(rest) = line.strip_prefix("CHARS ") {
+            font.charcount = bdf_num(rest, lineno, "CHARS", line)?;
+            chars_line = Some(lineno);
             i += 1;
-        } else if line.starts_with("STARTCHAR ") {
+        } else if let Some(rest) = line.strip_prefix("STARTCHAR ") {
             let mut glyph = BDFGlyph {
-                startchar: line[10..].to_string(),
-                encoding: 0,
+                startchar: rest.to_string(),
+                encoding: None,
+                alt_encoding: None,
                 swidth: (0, 0),
                 dwidth: (0, 0),
+                swidth1: None,
+                dwidth1: None,
+                vvector: None,
                 bbx: (0, 0, 0, 0),
                 bitmap: Vec::new(),
             };
+            let mut ended = false;
             i += 1;
             while i < lines.len() {
                 let line = lines[i];
-                if line.starts_with("ENCODING ") {
-                    glyph.encoding = line[9..].parse::<usize>()?;
+                let lineno = i + 1;
+                if let Some(rest) = line.strip_prefix("ENCODING ") {
+                    let parts = bdf_fields(rest, 1, lineno, "ENCODING", line)?;
+                    let code: i64 = bdf_num(parts[0], lineno, "ENCODING", line)?;
+                    glyph.encoding = match code {
+                        -1 => None,
+                        _ => Some(u32::try_from(code).map_err(|_| BdfError::MalformedField {
+                            line: lineno,
+                            keyword: "ENCODING",
+                            text: line.to_string(),
+                        })?),
+                    };
+                    glyph.alt_encoding = match parts.get(1) {
+                        Some(alt) => Some(bdf_num(alt, lineno, "ENCODING", line)?),
+                        None => None,
+                    };
+                    i += 1;
+                } else if let Some(rest) = line.strip_prefix("SWIDTH ") {
+                    glyph.swidth = bdf_pair(rest, lineno, "SWIDTH", line)?;
+                    i += 1;
+                } else if let Some(rest) = line.strip_prefix("DWIDTH ") {
+                    glyph.dwidth = bdf_pair(rest, lineno, "DWIDTH", line)?;
                     i += 1;
-                } else if line.starts_with("SWIDTH ") {
-                    let parts: Vec<&str> = line[7..].split_whitespace().collect();
-                    glyph.swidth = (parts[0].parse::<usize>()?, parts[1].parse::<usize>()?);
+                } else if let Some(rest) = line.strip_prefix("SWIDTH1 ") {
+                    glyph.swidth1 = Some(bdf_pair(rest, lineno, "SWIDTH1", line)?);
                     i += 1;
-                } else if line.starts_with("DWIDTH ") {
-                    let parts: Vec<&str> = line[7..].split_whitespace().collect();
-                    glyph.dwidth = (parts[0].parse::<usize>()?, parts[1].parse::<usize>()?);
+                } else if let Some(rest) = line.strip_prefix("DWIDTH1 ") {
+                    glyph.dwidth1 = Some(bdf_pair(rest, lineno, "DWIDTH1", line)?);
                     i += 1;
-                } else if line.starts_with("BBX ") {
-                    let parts: Vec<&str> = line[4..].split_whitespace().collect();
+                } else if let Some(rest) = line.strip_prefix("VVECTOR ") {
+                    glyph.vvector = Some(bdf_pair(rest, lineno, "VVECTOR", line)?);
+                    i += 1;
+                } else if let Some(rest) = line.strip_prefix("BBX ") {
+                    let parts = bdf_fields(rest, 4, lineno, "BBX", line)?;
                     glyph.bbx = (
-                        parts[0].parse::<usize>()?,
-                        parts[1].parse::<usize>()?,
-                        parts[2].parse::<isize>()?,
-                        parts[3].parse::<isize>()?,
+                        bdf_num(parts[0], lineno, "BBX", line)?,
+                        bdf_num(parts[1], lineno, "BBX", line)?,
+                        bdf_num(parts[2], lineno, "BBX", line)?,
+                        bdf_num(parts[3], lineno, "BBX", line)?,
                     );
                     i += 1;
                 } else if line == "BITMAP" {
+                    let stride = glyph.row_bytes();
                     i += 1;
                     while i < lines.len() && lines[i] != "ENDCHAR" {
-                        let hex_str = lines[i];
-                        let value = u16::from_str_radix(hex_str, 16)?;
-
-                        let normalized_value = if hex_str.len() <= 2 {
-                            value << 8
-                        } else {
-                            value
+                        let hex_str = lines[i].trim();
+                        let bad_row = || BdfError::BadBitmapRow {
+                            line: i + 1,
+                            text: lines[i].to_string(),
                         };
-                        glyph.bitmap.push(normalized_value);
+                        if !hex_str.len().is_multiple_of(2) || !hex_str.is_ascii() {
+                            return Err(bad_row());
+                        }
+
+                        let mut row = Vec::with_capacity(stride);
+                        for pair in hex_str.as_bytes().chunks(2) {
+                            let pair = std::str::from_utf8(pair).map_err(|_| bad_row())?;
+                            row.push(u8::from_str_radix(pair, 16).map_err(|_| bad_row())?);
+                        }
+                        // Rows are padded to a byte boundary but some writers
+                        // pad further; keep exactly the bytes the BBX needs.
+                        row.resize(stride, 0);
+                        glyph.bitmap.extend_from_slice(&row);
                         i += 1;
                     }
+                    ended = i < lines.len();
+                    i += 1;
+                    break;
+                } else if line == "ENDCHAR" {
+                    ended = true;
                     i += 1;
                     break;
                 } else {
                     i += 1;
                 }
             }
+            if !ended {
+                return Err(BdfError::UnexpectedEof {
+                    line: lines.len(),
+                    expected: "ENDCHAR",
+                });
+            }
             font.glyphs.push(glyph);
         } else if line == "ENDFONT" {
-            break;
+            if let Some(chars_line) = chars_line
+                && font.glyphs.len() != font.charcount
+            {
+                return Err(BdfError::CharsMismatch {
+                    line: chars_line,
+                    declared: font.charcount,
+                    found: font.glyphs.len(),
+                });
+            }
+            return Ok(font);
         } else {
             i += 1;
         }
     }
 
-    Ok(font)
+    Err(BdfError::UnexpectedEof {
+        line: lines.len(),
+        expected: "ENDFONT",
+    })
+}
+
+impl BDFFont {
+    /// The declared charset, from the `CHARSET_*` properties or else the
+    /// XLFD font name. Fonts that declare neither are treated as Unicode.
+    pub fn charset(&self) -> Charset {
+        let props = &self.properties;
+        if let Some(charset) =
+            Charset::from_xlfd(props.charset_registry(), props.charset_encoding())
+        {
+            return charset;
+        }
+        let mut fields = self.font.rsplit('-');
+        match (fields.next(), fields.next()) {
+            (Some(encoding), Some(registry)) => Charset::from_xlfd(registry, encoding),
+            _ => None,
+        }
+        .unwrap_or(Charset::Unicode)
+    }
+
+    /// Returns a copy containing only the glyphs for `chars` (mapped through
+    /// the font's charset), plus the `DEFAULT_CHAR` glyph if the font
+    /// declares one. `CHARS` and `AVERAGE_WIDTH` are recomputed for the
+    /// remaining glyphs.
+    pub fn subset(&self, chars: impl IntoIterator<Item = char>) -> BDFFont {
+        let charset = self.charset();
+        let mut wanted: std::collections::HashSet<u32> = chars
+            .into_iter()
+            .filter_map(|c| charset.from_unicode(c))
+            .collect();
+        if let Some(default) = self.properties.get_int("DEFAULT_CHAR")
+            && let Ok(code) = u32::try_from(default)
+        {
+            wanted.insert(code);
+        }
+
+        let glyphs: Vec<BDFGlyph> = self
+            .glyphs
+            .iter()
+            .filter(|glyph| glyph.encoding.is_some_and(|enc| wanted.contains(&enc)))
+            .cloned()
+            .collect();
+
+        let mut properties = self.properties.clone();
+        if properties.get("AVERAGE_WIDTH").is_some() && !glyphs.is_empty() {
+            // AVERAGE_WIDTH is in tenths of a pixel.
+            let total: isize = glyphs.iter().map(|g| g.dwidth.0.abs() * 10).sum();
+            let average = (total as f64 / glyphs.len() as f64).round() as i64;
+            properties.set("AVERAGE_WIDTH", BDFPropertyValue::Int(average));
+        }
+
+        BDFFont {
+            size: self.size,
+            font: self.font.clone(),
+            bounding_box: self.bounding_box,
+            charcount: glyphs.len(),
+            properties,
+            glyphs,
+        }
+    }
+
+    pub fn to_bdf_string(&self) -> String {
+        let mut out = Vec::new();
+        write_bdf(self, &mut out).expect("writing to a Vec cannot fail");
+        String::from_utf8(out).expect("BDF output is UTF-8")
+    }
+}
+
+/// Serializes `font` as a BDF 2.1 file. `CHARS` is written from the actual
+/// glyph count rather than `charcount`, so edited fonts stay consistent.
+pub fn write_bdf(font: &BDFFont, mut out: impl std::io::Write) -> std::io::Result<()> {
+    writeln!(out, "STARTFONT 2.1")?;
+    writeln!(out, "FONT {}", font.font)?;
+    writeln!(out, "SIZE {} {} {}", font.size.0, font.size.1, font.size.2)?;
+    let (w, h, x, y) = font.bounding_box;
+    writeln!(out, "FONTBOUNDINGBOX {w} {h} {x} {y}")?;
+
+    if !font.properties.is_empty() {
+        writeln!(out, "STARTPROPERTIES {}", font.properties.len())?;
+        for (name, value) in &font.properties.entries {
+            match value {
+                BDFPropertyValue::Int(v) => writeln!(out, "{name} {v}")?,
+                BDFPropertyValue::Str(v) => writeln!(out, "{name} \"{}\"", v.replace('"', "\"\""))?,
+            }
+        }
+        writeln!(out, "ENDPROPERTIES")?;
+    }
+
+    writeln!(out, "CHARS {}", font.glyphs.len())?;
+    for glyph in &font.glyphs {
+        writeln!(out, "STARTCHAR {}", glyph.startchar)?;
+        match (glyph.encoding, glyph.alt_encoding) {
+            (Some(enc), None) => writeln!(out, "ENCODING {enc}")?,
+            (Some(enc), Some(alt)) => writeln!(out, "ENCODING {enc} {alt}")?,
+            (None, None) => writeln!(out, "ENCODING -1")?,
+            (None, Some(alt)) => writeln!(out, "ENCODING -1 {alt}")?,
+        }
+        writeln!(out, "SWIDTH {} {}", glyph.swidth.0, glyph.swidth.1)?;
+        writeln!(out, "DWIDTH {} {}", glyph.dwidth.0, glyph.dwidth.1)?;
+        if let Some((x, y)) = glyph.swidth1 {
+            writeln!(out, "SWIDTH1 {x} {y}")?;
+        }
+        if let Some((x, y)) = glyph.dwidth1 {
+            writeln!(out, "DWIDTH1 {x} {y}")?;
+        }
+        if let Some((x, y)) = glyph.vvector {
+            writeln!(out, "VVECTOR {x} {y}")?;
+        }
+        let (w, h, x, y) = glyph.bbx;
+        writeln!(out, "BBX {w} {h} {x} {y}")?;
+        writeln!(out, "BITMAP")?;
+        for row in 0..h {
+            let bytes = glyph.row(row);
+            for col in 0..glyph.row_bytes() {
+                write!(out, "{:02X}", bytes.get(col).copied().unwrap_or(0))?;
+            }
+            writeln!(out)?;
+        }
+        writeln!(out, "ENDCHAR")?;
+    }
+    writeln!(out, "ENDFONT")
 }
 
 pub fn bdf_to_rects(glyph: &BDFGlyph) -> Vec<Vec<(f32, f32)>> {
     let mut rects = Vec::new();
     let (_, height, x_off, y_off) = glyph.bbx;
 
-    for (row_idx, &row_data) in glyph.bitmap.iter().enumerate() {
+    for row_idx in 0..height {
         for col_idx in 0..glyph.bbx.0 {
-            if (row_data >> (15 - col_idx)) & 1 != 0 {
+            if glyph.pixel(col_idx, row_idx) {
                 let x = (x_off + col_idx as isize) as f32;
                 let y = (y_off + (height as isize - 1 - row_idx as isize)) as f32;
 
@@ -224,43 +649,45 @@ pub fn bdf_to_rects(glyph: &BDFGlyph) -> Vec<Vec<(f32, f32)>> {
     rects
 }
 
-pub fn bdf_to_curves(bdf: &BDFFont) -> Vec<(String, usize, Vec<Vec<(f32, f32)>>)> {
-    bdf.glyphs
-        .iter()
-        .map(|glyph| {
-            let mut paths = Vec::new();
-            let (bbx_width, bbx_height, x_offset, y_offset) = glyph.bbx;
+/// Horizontal run segments for one glyph, top-down with the baseline at
+/// `font_ascent`.
+pub fn bdf_glyph_runs(glyph: &BDFGlyph, font_ascent: isize) -> Vec<Vec<(f32, f32)>> {
+    let mut paths = Vec::new();
+    let (bbx_width, bbx_height, x_offset, y_offset) = glyph.bbx;
 
-            let font_ascent = bdf.properties.font_ascent as isize;
+    for row_idx in 0..bbx_height {
+        let y = (font_ascent - y_offset - bbx_height as isize + row_idx as isize) as f32;
 
-            for (row_idx, &row_bits) in glyph.bitmap.iter().enumerate() {
-                let y = (font_ascent - y_offset - bbx_height as isize + row_idx as isize) as f32;
+        let mut start_col: Option<usize> = None;
 
-                let mut start_col: Option<usize> = None;
+        for col_idx in 0..bbx_width {
+            let bit_is_set = glyph.pixel(col_idx, row_idx);
 
-                for col_idx in 0..bbx_width {
-                    let bit_is_set = (row_bits >> (15 - col_idx)) & 1 != 0;
-
-                    match (bit_is_set, start_col) {
-                        (true, None) => start_col = Some(col_idx),
-                        (false, Some(start)) => {
-                            let x1 = (x_offset + start as isize) as f32;
-                            let x2 = (x_offset + col_idx as isize) as f32;
-                            paths.push(vec![(x1, y), (x2, y)]);
-                            start_col = None;
-                        }
-                        _ => {}
-                    }
-                }
-
-                if let Some(start) = start_col {
-                    let x1 = (x_offset + start as isize) as f32;
-                    let x2 = (x_offset + bbx_width as isize) as f32;
-                    paths.push(vec![(x1, y), (x2, y)]);
+            match (bit_is_set, start_col) {
+                (true, None) => start_col = Some(col_idx),
+                (false, Some(start)) => {
+                    paths.push(create_line(start, col_idx, x_offset, y));
+                    start_col = None;
                 }
+                _ => {}
             }
+        }
+
+        if let Some(start) = start_col {
+            paths.push(create_line(start, bbx_width, x_offset, y));
+        }
+    }
+    paths
+}
 
-            (glyph.encoding.to_string(), glyph.dwidth.0, paths)
+pub fn bdf_to_curves(bdf: &BDFFont) -> Curves {
+    let font_ascent = bdf.properties.font_ascent() as isize;
+    bdf.glyphs
+        .iter()
+        .filter_map(|glyph| {
+            let encoding = glyph.encoding?;
+            let paths = bdf_glyph_runs(glyph, font_ascent);
+            Some((encoding.to_string(), glyph.dwidth.0, paths))
         })
         .collect()
 }
@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+
+use crate::BDFGlyph;
+
+// Edge directions on the pixel grid, counterclockwise from +x.
+const RIGHT: u8 = 0;
+const UP: u8 = 1;
+const LEFT: u8 = 2;
+const DOWN: u8 = 3;
+
+fn step((x, y): (i32, i32), dir: u8) -> (i32, i32) {
+    match dir {
+        RIGHT => (x + 1, y),
+        UP => (x, y + 1),
+        LEFT => (x - 1, y),
+        _ => (x, y - 1),
+    }
+}
+
+/// Traces the glyph bitmap into closed polygons, in the same y-up coordinates
+/// as `bdf_to_rects`. Outer contours wind counterclockwise and holes
+/// clockwise, so the filled area is always on the left of each edge. Pixels
+/// that only touch at a corner become separate contours. Each polygon repeats
+/// its first point at the end, and collinear points are removed.
+pub fn bdf_to_outlines(glyph: &BDFGlyph) -> Vec<Vec<(f32, f32)>> {
+    let (width, height, x_off, y_off) = glyph.bbx;
+    // Grid coordinates: column, and row counted upward from the BBX bottom.
+    let filled = |x: i32, y: i32| {
+        x >= 0
+            && y >= 0
+            && (x as usize) < width
+            && (y as usize) < height
+            && glyph.pixel(x as usize, height - 1 - y as usize)
+    };
+
+    // Boundary edges keyed by start vertex and direction; value is "used".
+    let mut edges: HashMap<((i32, i32), u8), bool> = HashMap::new();
+    let mut order = Vec::new();
+    for y in 0..height as i32 {
+        for x in 0..width as i32 {
+            if !filled(x, y) {
+                continue;
+            }
+            let sides = [
+                (!filled(x, y - 1), (x, y), RIGHT),
+                (!filled(x + 1, y), (x + 1, y), UP),
+                (!filled(x, y + 1), (x + 1, y + 1), LEFT),
+                (!filled(x - 1, y), (x, y + 1), DOWN),
+            ];
+            for (exposed, start, dir) in sides {
+                if exposed {
+                    edges.insert((start, dir), false);
+                    order.push((start, dir));
+                }
+            }
+        }
+    }
+
+    let mut outlines = Vec::new();
+    for first in order {
+        if edges[&first] {
+            continue;
+        }
+
+        let mut corners = Vec::new();
+        let (mut vertex, mut dir) = first;
+        loop {
+            edges.insert((vertex, dir), true);
+            let next_vertex = step(vertex, dir);
+            // Prefer the left turn so diagonal neighbours stay separate.
+            let next_dir = [(dir + 1) % 4, dir, (dir + 3) % 4]
+                .into_iter()
+                .find(|&d| edges.contains_key(&(next_vertex, d)))
+                .expect("boundary edges always form closed loops");
+            if next_dir != dir {
+                corners.push(next_vertex);
+            }
+            if (next_vertex, next_dir) == first {
+                break;
+            }
+            vertex = next_vertex;
+            dir = next_dir;
+        }
+
+        // The loop closes on its start vertex; list that corner first.
+        if corners.last() == Some(&first.0) {
+            corners.rotate_right(1);
+        }
+
+        let mut polygon: Vec<(f32, f32)> = corners
+            .into_iter()
+            .map(|(x, y)| ((x_off + x as isize) as f32, (y_off + y as isize) as f32))
+            .collect();
+        if let Some(&start) = polygon.first() {
+            polygon.push(start);
+            outlines.push(polygon);
+        }
+    }
+    outlines
+}
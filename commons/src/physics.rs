@@ -0,0 +1,231 @@
+type Point = (f32, f32);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Particle {
+    pub x: f32,
+    pub y: f32,
+    pub vx: f32,
+    pub vy: f32,
+}
+
+impl Particle {
+    pub fn at(x: f32, y: f32) -> Self {
+        Self {
+            x,
+            y,
+            ..Default::default()
+        }
+    }
+}
+
+/// A point that pulls particles in (positive `strength`) or pushes them away
+/// (negative). The force fades linearly to zero at `radius`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Attractor {
+    pub x: f32,
+    pub y: f32,
+    pub strength: f32,
+    pub radius: f32,
+}
+
+/// Forces acting on every particle. Accelerations are in pixels per second
+/// squared; rates are per second.
+#[derive(Clone, Debug)]
+pub struct PhysicsParams {
+    /// Spring constant pulling each particle toward its target.
+    pub stiffness: f32,
+    /// Damping on velocity relative to the target. Below
+    /// `2 * sqrt(stiffness)` the spring overshoots and wobbles.
+    pub damping: f32,
+    /// Strength of the random-direction noise force.
+    pub noise_strength: f32,
+    /// Strength of the divergence-free curl-noise force, which swirls
+    /// particles without bunching them up.
+    pub curl_strength: f32,
+    /// Noise features per pixel; smaller values give larger eddies.
+    pub noise_scale: f32,
+    /// How fast the noise field drifts over time.
+    pub noise_speed: f32,
+    pub gravity: Point,
+    /// Velocity of the surrounding air, which drags particles along at
+    /// `drag` per second.
+    pub wind: Point,
+    pub drag: f32,
+    pub attractors: Vec<Attractor>,
+}
+
+impl Default for PhysicsParams {
+    fn default() -> Self {
+        Self {
+            stiffness: 60.0,
+            damping: 9.0,
+            noise_strength: 0.0,
+            curl_strength: 0.0,
+            noise_scale: 0.01,
+            noise_speed: 0.5,
+            gravity: (0.0, 0.0),
+            wind: (0.0, 0.0),
+            drag: 0.0,
+            attractors: Vec::new(),
+        }
+    }
+}
+
+/// Accumulates frame times into whole steps of a fixed size so simulations
+/// behave the same at any frame rate.
+#[derive(Clone, Copy, Debug)]
+pub struct FixedTimestep {
+    pub step: f32,
+    /// Upper bound on steps per `advance`; time beyond it is dropped so a
+    /// long stall can't snowball into ever slower frames.
+    pub max_steps: u32,
+    accumulator: f32,
+    /// Steps taken since time zero.
+    steps: u64,
+}
+
+impl FixedTimestep {
+    pub fn new(step: f32) -> Self {
+        Self {
+            step,
+            max_steps: 8,
+            accumulator: 0.0,
+            steps: 0,
+        }
+    }
+
+    /// Adds `dt` seconds and returns how many steps to run.
+    pub fn advance(&mut self, dt: f32) -> u32 {
+        self.accumulator += dt.max(0.0);
+        let mut steps = (self.accumulator / self.step) as u32;
+        if steps > self.max_steps {
+            self.accumulator = 0.0;
+            steps = self.max_steps;
+        } else {
+            self.accumulator -= steps as f32 * self.step;
+        }
+        self.steps += steps as u64;
+        steps
+    }
+
+    /// Moves up to `time` seconds after time zero and returns how many steps
+    /// to run. Steps are counted from time zero rather than accumulated, so
+    /// a given time always lands on the same step however the frames fell.
+    /// Going back in time runs no steps; `seek` for that.
+    pub fn advance_to(&mut self, time: f32) -> u32 {
+        let target = (time / self.step) as u64;
+        self.steps = self.steps.max(target.saturating_sub(self.max_steps as u64));
+        let steps = target.saturating_sub(self.steps);
+        self.steps += steps;
+        self.accumulator = 0.0;
+        steps as u32
+    }
+
+    /// Jumps to `time` seconds after time zero without running any steps.
+    pub fn seek(&mut self, time: f32) {
+        self.steps = (time / self.step) as u64;
+        self.accumulator = 0.0;
+    }
+
+    /// Steps taken since time zero, counting the ones `advance` and
+    /// `advance_to` just returned.
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// Fraction of a step left over, for interpolating between states.
+    pub fn alpha(&self) -> f32 {
+        self.accumulator / self.step
+    }
+}
+
+/// Advances every particle by one step of `h` seconds with semi-implicit
+/// Euler. `target(i)` gives particle `i`'s spring anchor and `time` drives
+/// the noise field.
+pub fn step_particles(
+    particles: &mut [Particle],
+    target: impl Fn(usize) -> Point,
+    params: &PhysicsParams,
+    time: f32,
+    h: f32,
+) {
+    let drift = time * params.noise_speed;
+    for (i, p) in particles.iter_mut().enumerate() {
+        let (tx, ty) = target(i);
+        let mut ax = params.stiffness * (tx - p.x) - params.damping * p.vx;
+        let mut ay = params.stiffness * (ty - p.y) - params.damping * p.vy;
+
+        ax += params.gravity.0 + params.drag * (params.wind.0 - p.vx);
+        ay += params.gravity.1 + params.drag * (params.wind.1 - p.vy);
+
+        if params.noise_strength != 0.0 || params.curl_strength != 0.0 {
+            let (nx, ny) = (p.x * params.noise_scale + drift, p.y * params.noise_scale);
+            ax += params.noise_strength * gradient_noise(nx, ny, 0);
+            ay += params.noise_strength * gradient_noise(nx, ny, 1);
+            let (cx, cy) = curl_noise(nx, ny, 2);
+            ax += params.curl_strength * cx;
+            ay += params.curl_strength * cy;
+        }
+
+        for a in &params.attractors {
+            let (dx, dy) = (a.x - p.x, a.y - p.y);
+            let dist = (dx * dx + dy * dy).sqrt();
+            if dist > 1e-3 && dist < a.radius {
+                let falloff = a.strength * (1.0 - dist / a.radius) / dist;
+                ax += dx * falloff;
+                ay += dy * falloff;
+            }
+        }
+
+        p.vx += ax * h;
+        p.vy += ay * h;
+        p.x += p.vx * h;
+        p.y += p.vy * h;
+    }
+}
+
+/// 2D Perlin-style gradient noise in roughly `[-1, 1]`, one independent
+/// field per `seed`.
+pub fn gradient_noise(x: f32, y: f32, seed: u32) -> f32 {
+    let (x0, y0) = (x.floor(), y.floor());
+    let (fx, fy) = (x - x0, y - y0);
+    let (ix, iy) = (x0 as i32, y0 as i32);
+    let corner = |cx: i32, cy: i32, dx: f32, dy: f32| {
+        let angle = hash(ix + cx, iy + cy, seed) as f32 * (std::f32::consts::TAU / 4294967296.0);
+        angle.cos() * dx + angle.sin() * dy
+    };
+    let fade = |t: f32| t * t * t * (t * (t * 6.0 - 15.0) + 10.0);
+    let (u, v) = (fade(fx), fade(fy));
+
+    let bottom = lerp(corner(0, 0, fx, fy), corner(1, 0, fx - 1.0, fy), u);
+    let top = lerp(
+        corner(0, 1, fx, fy - 1.0),
+        corner(1, 1, fx - 1.0, fy - 1.0),
+        u,
+    );
+    // Unit gradients peak at sqrt(1/2); rescale to about [-1, 1].
+    lerp(bottom, top, v) * std::f32::consts::SQRT_2
+}
+
+/// Curl of a gradient-noise potential: a divergence-free flow field.
+pub fn curl_noise(x: f32, y: f32, seed: u32) -> Point {
+    let e = 1e-2;
+    let dx = (gradient_noise(x + e, y, seed) - gradient_noise(x - e, y, seed)) / (2.0 * e);
+    let dy = (gradient_noise(x, y + e, seed) - gradient_noise(x, y - e, seed)) / (2.0 * e);
+    (dy, -dx)
+}
+
+fn hash(x: i32, y: i32, seed: u32) -> u32 {
+    let mut h = (x as u32).wrapping_mul(0x8DA6_B343)
+        ^ (y as u32).wrapping_mul(0xD816_3841)
+        ^ seed.wrapping_mul(0xCB1A_B31F);
+    h ^= h >> 16;
+    h = h.wrapping_mul(0x7FEB_352D);
+    h ^= h >> 15;
+    h = h.wrapping_mul(0x846C_A68B);
+    h ^ (h >> 16)
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
@@ -0,0 +1,266 @@
+use crate::GlyphSet;
+use crate::layout::TextLayout;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SampleRegion {
+    /// The lit pixels of each glyph.
+    Fill,
+    /// The traced glyph outlines.
+    Outline,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SampleMethod {
+    /// One jittered sample per equal-area (or equal-length) stratum. Fast and
+    /// even, but neighbouring points can still land close together.
+    Stratified,
+    /// Mitchell's best-candidate algorithm: each point is the candidate
+    /// furthest from those already placed, giving Poisson-disk-like spacing.
+    BlueNoise { candidates: usize },
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SampleOptions {
+    pub region: SampleRegion,
+    pub method: SampleMethod,
+    pub seed: u64,
+}
+
+impl Default for SampleOptions {
+    fn default() -> Self {
+        Self {
+            region: SampleRegion::Fill,
+            method: SampleMethod::Stratified,
+            seed: 0,
+        }
+    }
+}
+
+/// Small deterministic generator (SplitMix64) so samples are reproducible
+/// from a seed without pulling in a dependency.
+#[derive(Clone, Debug)]
+pub struct SplitMix64(u64);
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `[0, 1)`.
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+/// A shape reduced to pieces that can be sampled by a single measure: filled
+/// rectangles by area, or outline segments by length.
+enum Piece {
+    Rect { x: f32, y: f32, w: f32, h: f32 },
+    Segment { from: (f32, f32), to: (f32, f32) },
+}
+
+impl Piece {
+    fn measure(&self) -> f32 {
+        match *self {
+            Piece::Rect { w, h, .. } => w * h,
+            Piece::Segment { from, to } => {
+                ((to.0 - from.0).powi(2) + (to.1 - from.1).powi(2)).sqrt()
+            }
+        }
+    }
+
+    /// Maps `offset` in `[0, measure)` plus a jitter in `[0, 1)` to a point.
+    /// Rectangles are swept column by column so consecutive offsets stay
+    /// spatially close.
+    fn point_at(&self, offset: f32, jitter: f32) -> (f32, f32) {
+        match *self {
+            Piece::Rect { x, y, w, h } => ((x + offset / h).min(x + w), y + jitter * h),
+            Piece::Segment { from, to } => {
+                let t = offset / self.measure().max(f32::EPSILON);
+                (from.0 + (to.0 - from.0) * t, from.1 + (to.1 - from.1) * t)
+            }
+        }
+    }
+}
+
+/// Produces exactly `count` points spread evenly over a laid-out string, in
+/// the layout's output coordinates. Returns no points if the text has no
+/// lit pixels.
+pub fn sample_text(
+    layout: &TextLayout,
+    glyphs: &GlyphSet,
+    count: usize,
+    options: &SampleOptions,
+) -> Vec<(f32, f32)> {
+    let mut pieces = Vec::new();
+    for placed in &layout.glyphs {
+        let Some(glyph) = glyphs.get(placed.ch) else {
+            continue;
+        };
+        let s = placed.scale;
+        let at = |(x, y): (f32, f32)| (placed.x + x * s, placed.y + y * s);
+        match options.region {
+            SampleRegion::Fill => {
+                for run in &glyph.paths {
+                    let ((x1, y), (x2, _)) = (at(run[0]), at(run[run.len() - 1]));
+                    pieces.push(Piece::Rect {
+                        x: x1,
+                        y,
+                        w: x2 - x1,
+                        h: s,
+                    });
+                }
+            }
+            SampleRegion::Outline => {
+                for outline in glyph.outlines() {
+                    for edge in outline.windows(2) {
+                        pieces.push(Piece::Segment {
+                            from: at(edge[0]),
+                            to: at(edge[1]),
+                        });
+                    }
+                }
+            }
+        }
+    }
+    sample_pieces(&pieces, count, options)
+}
+
+fn sample_pieces(pieces: &[Piece], count: usize, options: &SampleOptions) -> Vec<(f32, f32)> {
+    let mut cumulative = Vec::with_capacity(pieces.len());
+    let mut total = 0.0;
+    for piece in pieces {
+        total += piece.measure();
+        cumulative.push(total);
+    }
+    if count == 0 || total <= 0.0 {
+        return Vec::new();
+    }
+
+    let mut rng = SplitMix64::new(options.seed);
+    let locate = |u: f32, jitter: f32| {
+        let u = u.clamp(0.0, total * (1.0 - f32::EPSILON));
+        let idx = cumulative
+            .partition_point(|&c| c <= u)
+            .min(pieces.len() - 1);
+        let start = if idx == 0 { 0.0 } else { cumulative[idx - 1] };
+        pieces[idx].point_at(u - start, jitter)
+    };
+
+    match options.method {
+        SampleMethod::Stratified => (0..count)
+            .map(|i| {
+                let u = (i as f32 + rng.next_f32()) / count as f32 * total;
+                locate(u, rng.next_f32())
+            })
+            .collect(),
+        SampleMethod::BlueNoise { candidates } => {
+            let candidates = candidates.max(1);
+            let mut grid = PointGrid::new(pieces, total / count as f32);
+            let mut points = Vec::with_capacity(count);
+            for _ in 0..count {
+                let mut best = locate(rng.next_f32() * total, rng.next_f32());
+                let mut best_dist = grid.nearest_sq(best);
+                for _ in 1..candidates {
+                    let candidate = locate(rng.next_f32() * total, rng.next_f32());
+                    let dist = grid.nearest_sq(candidate);
+                    if dist > best_dist {
+                        best = candidate;
+                        best_dist = dist;
+                    }
+                }
+                grid.insert(best);
+                points.push(best);
+            }
+            points
+        }
+    }
+}
+
+/// Uniform grid over the pieces' bounds for nearest-neighbour queries.
+struct PointGrid {
+    origin: (f32, f32),
+    cell: f32,
+    cols: usize,
+    rows: usize,
+    cells: Vec<Vec<(f32, f32)>>,
+}
+
+impl PointGrid {
+    fn new(pieces: &[Piece], measure_per_point: f32) -> Self {
+        let (mut min, mut max) = ((f32::MAX, f32::MAX), (f32::MIN, f32::MIN));
+        for piece in pieces {
+            let (a, b) = match *piece {
+                Piece::Rect { x, y, w, h } => ((x, y), (x + w, y + h)),
+                Piece::Segment { from, to } => (from, to),
+            };
+            for (px, py) in [a, b] {
+                min = (min.0.min(px), min.1.min(py));
+                max = (max.0.max(px), max.1.max(py));
+            }
+        }
+        // Roughly one point per cell: the expected spacing is sqrt(area per
+        // point) for fills and the length per point for outlines.
+        let spacing = match pieces.first() {
+            Some(Piece::Rect { .. }) => measure_per_point.sqrt(),
+            _ => measure_per_point,
+        };
+        // Cap the grid at 1024 cells a side by growing the cells instead.
+        let extent = (max.0 - min.0).max(max.1 - min.1);
+        let cell = spacing.max(extent / 1023.0).max(1e-3);
+        let cols = ((max.0 - min.0) / cell) as usize + 1;
+        let rows = ((max.1 - min.1) / cell) as usize + 1;
+        Self {
+            origin: min,
+            cell,
+            cols,
+            rows,
+            cells: vec![Vec::new(); cols * rows],
+        }
+    }
+
+    fn cell_of(&self, (x, y): (f32, f32)) -> (usize, usize) {
+        let cx = ((x - self.origin.0) / self.cell).max(0.0) as usize;
+        let cy = ((y - self.origin.1) / self.cell).max(0.0) as usize;
+        (cx.min(self.cols - 1), cy.min(self.rows - 1))
+    }
+
+    fn insert(&mut self, p: (f32, f32)) {
+        let (cx, cy) = self.cell_of(p);
+        self.cells[cy * self.cols + cx].push(p);
+    }
+
+    /// Squared distance to the nearest inserted point, or infinity.
+    fn nearest_sq(&self, p: (f32, f32)) -> f32 {
+        let (cx, cy) = self.cell_of(p);
+        let mut best = f32::INFINITY;
+        for ring in 0..self.cols.max(self.rows) {
+            // Points beyond this ring are at least `ring - 1` cells away.
+            let reach = ring.saturating_sub(1) as f32 * self.cell;
+            if best <= reach * reach {
+                break;
+            }
+            let (x0, x1) = (cx.saturating_sub(ring), (cx + ring).min(self.cols - 1));
+            let (y0, y1) = (cy.saturating_sub(ring), (cy + ring).min(self.rows - 1));
+            for y in y0..=y1 {
+                for x in x0..=x1 {
+                    if x.abs_diff(cx).max(y.abs_diff(cy)) != ring {
+                        continue;
+                    }
+                    for q in &self.cells[y * self.cols + x] {
+                        best = best.min((q.0 - p.0).powi(2) + (q.1 - p.1).powi(2));
+                    }
+                }
+            }
+        }
+        best
+    }
+}
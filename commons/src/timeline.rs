@@ -0,0 +1,256 @@
+use crate::easing::Easing;
+
+/// Values a `Track` can interpolate between.
+pub trait Lerp: Copy {
+    /// `self` at `t == 0`, `other` at `t == 1`.
+    fn lerp(self, other: Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    fn lerp(self, other: Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+}
+
+/// Points.
+impl Lerp for (f32, f32) {
+    fn lerp(self, other: Self, t: f32) -> Self {
+        (self.0.lerp(other.0, t), self.1.lerp(other.1, t))
+    }
+}
+
+/// Colors (RGB or RGBA) and other vectors, component-wise.
+impl<const N: usize> Lerp for [f32; N] {
+    fn lerp(self, other: Self, t: f32) -> Self {
+        std::array::from_fn(|i| self[i].lerp(other[i], t))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Keyframe<T> {
+    /// Seconds.
+    pub time: f32,
+    pub value: T,
+    /// How the value moves into this keyframe from the one before it.
+    pub easing: Easing,
+}
+
+/// A value animated by keyframes. Between two keyframes the value moves
+/// with the later keyframe's easing; before the first and after the last it
+/// holds. Two keyframes at the same time make an instant cut.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Track<T> {
+    keys: Vec<Keyframe<T>>,
+    looping: bool,
+}
+
+impl<T: Lerp> Track<T> {
+    /// A track that holds `value` from time 0 until keyframes are added.
+    pub fn new(value: T) -> Self {
+        Self {
+            keys: vec![Keyframe {
+                time: 0.0,
+                value,
+                easing: Easing::Linear,
+            }],
+            looping: false,
+        }
+    }
+
+    /// Adds a keyframe reaching `value` at `time`. Keyframes may be added in
+    /// any order; one added at the same time as an existing keyframe goes
+    /// after it.
+    pub fn key(mut self, time: f32, value: T, easing: Easing) -> Self {
+        let at = self.keys.partition_point(|k| k.time <= time);
+        self.keys.insert(
+            at,
+            Keyframe {
+                time,
+                value,
+                easing,
+            },
+        );
+        self
+    }
+
+    /// Adds a keyframe reaching `value` `duration` seconds after the last
+    /// one.
+    pub fn then(self, duration: f32, value: T, easing: Easing) -> Self {
+        let time = self.end() + duration;
+        self.key(time, value, easing)
+    }
+
+    /// Holds the last value for `duration` seconds.
+    pub fn hold(self, duration: f32) -> Self {
+        let value = self.keys[self.keys.len() - 1].value;
+        self.then(duration, value, Easing::Linear)
+    }
+
+    /// Whether the keyframes repeat from `start()` once `end()` is reached.
+    pub fn looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    pub fn keys(&self) -> &[Keyframe<T>] {
+        &self.keys
+    }
+
+    /// Time of the first keyframe.
+    pub fn start(&self) -> f32 {
+        self.keys[0].time
+    }
+
+    /// Time of the last keyframe.
+    pub fn end(&self) -> f32 {
+        self.keys[self.keys.len() - 1].time
+    }
+
+    pub fn duration(&self) -> f32 {
+        self.end() - self.start()
+    }
+
+    /// The value at absolute `time`.
+    pub fn sample(&self, time: f32) -> T {
+        let time = if self.looping && self.duration() > 0.0 {
+            self.start() + (time - self.start()).rem_euclid(self.duration())
+        } else {
+            time
+        };
+        let next = self.keys.partition_point(|k| k.time <= time);
+        if next == 0 {
+            return self.keys[0].value;
+        }
+        let (a, Some(b)) = (&self.keys[next - 1], self.keys.get(next)) else {
+            return self.keys[next - 1].value;
+        };
+        let t = (time - a.time) / (b.time - a.time);
+        a.value.lerp(b.value, b.easing.apply(t))
+    }
+}
+
+/// Something scheduled on a `Timeline`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Span<T> {
+    /// Seconds.
+    pub start: f32,
+    pub duration: f32,
+    pub item: T,
+}
+
+impl<T> Span<T> {
+    pub fn end(&self) -> f32 {
+        self.start + self.duration
+    }
+}
+
+/// One span active at a queried time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Active<'a, T> {
+    /// Position of the span in the order it was added.
+    pub index: usize,
+    pub item: &'a T,
+    /// Seconds since the span started.
+    pub local: f32,
+    /// `local` as a fraction of the span's duration.
+    pub progress: f32,
+}
+
+/// Items (scenes, effects, ...) scheduled over time. Spans can follow each
+/// other, overlap or leave gaps, and the whole timeline can loop.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Timeline<T> {
+    spans: Vec<Span<T>>,
+    looping: bool,
+}
+
+impl<T> Default for Timeline<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Timeline<T> {
+    pub fn new() -> Self {
+        Self {
+            spans: Vec::new(),
+            looping: false,
+        }
+    }
+
+    /// Whether queries past `duration()` wrap back to time 0.
+    pub fn looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    /// Schedules `item` from `start` for `duration` seconds and returns its
+    /// index.
+    pub fn add(&mut self, start: f32, duration: f32, item: T) -> usize {
+        self.spans.push(Span {
+            start,
+            duration: duration.max(0.0),
+            item,
+        });
+        self.spans.len() - 1
+    }
+
+    /// Schedules `item` to start `gap` seconds after the most recently added
+    /// span ends; a negative `gap` overlaps the two.
+    pub fn after(&mut self, gap: f32, duration: f32, item: T) -> usize {
+        let start = self.spans.last().map_or(0.0, |s| s.end()) + gap;
+        self.add(start.max(0.0), duration, item)
+    }
+
+    /// Schedules `item` right after the most recently added span.
+    pub fn then(&mut self, duration: f32, item: T) -> usize {
+        self.after(0.0, duration, item)
+    }
+
+    pub fn spans(&self) -> &[Span<T>] {
+        &self.spans
+    }
+
+    /// When the last span ends.
+    pub fn duration(&self) -> f32 {
+        self.spans.iter().map(Span::end).fold(0.0, f32::max)
+    }
+
+    /// Maps absolute `time` into the timeline, wrapping if it loops.
+    pub fn local_time(&self, time: f32) -> f32 {
+        let duration = self.duration();
+        if self.looping && duration > 0.0 {
+            // `rem_euclid` can round up to `duration` itself.
+            let time = time.rem_euclid(duration);
+            if time < duration { time } else { 0.0 }
+        } else {
+            time
+        }
+    }
+
+    /// Spans covering absolute `time` (each from its start, inclusive, to its
+    /// end, exclusive), in the order they were added.
+    pub fn active(&self, time: f32) -> impl Iterator<Item = Active<'_, T>> {
+        let time = self.local_time(time);
+        self.spans
+            .iter()
+            .enumerate()
+            .filter(move |(_, s)| s.start <= time && time < s.end())
+            .map(move |(index, s)| Active {
+                index,
+                item: &s.item,
+                local: time - s.start,
+                progress: (time - s.start) / s.duration,
+            })
+    }
+
+    /// The most recently started span covering `time`, if any.
+    pub fn at(&self, time: f32) -> Option<Active<'_, T>> {
+        self.active(time).max_by(|a, b| {
+            let start = |x: &Active<T>| self.spans[x.index].start;
+            start(a).total_cmp(&start(b)).then(a.index.cmp(&b.index))
+        })
+    }
+}
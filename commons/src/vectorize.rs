@@ -0,0 +1,213 @@
+use crate::{BDFGlyph, bdf_to_outlines};
+
+type Point = (f32, f32);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PathSegment {
+    Line(Point),
+    /// Cubic Bézier with two control points, ending at the last point.
+    Cubic(Point, Point, Point),
+}
+
+/// A closed contour; the last segment ends back at `start`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BezierContour {
+    pub start: Point,
+    pub segments: Vec<PathSegment>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct VectorizeOptions {
+    /// Potrace's `alphamax`: vertices whose smoothness estimate reaches this
+    /// value stay sharp corners. 0 keeps every corner, values above 4/3 round
+    /// everything.
+    pub corner_threshold: f32,
+    /// Straighten one-pixel stair steps into diagonals before fitting curves.
+    pub smooth_steps: bool,
+}
+
+impl Default for VectorizeOptions {
+    fn default() -> Self {
+        Self {
+            corner_threshold: 1.0,
+            smooth_steps: true,
+        }
+    }
+}
+
+/// Converts a bitmap glyph into smooth closed Bézier contours in the same
+/// y-up coordinates and winding as `bdf_to_outlines`, fitting curves the way
+/// potrace does: each contour passes through the midpoints of its polygon
+/// edges and either bends around or keeps each vertex as a corner.
+pub fn vectorize_glyph(glyph: &BDFGlyph, options: &VectorizeOptions) -> Vec<BezierContour> {
+    bdf_to_outlines(glyph)
+        .into_iter()
+        .map(|mut polygon| {
+            polygon.pop();
+            if options.smooth_steps {
+                polygon = straighten_steps(polygon);
+            }
+            fit_contour(&polygon, options.corner_threshold)
+        })
+        .collect()
+}
+
+/// Approximates a contour with line segments no further than `tolerance`
+/// from the curve. The first point is repeated at the end.
+pub fn flatten_contour(contour: &BezierContour, tolerance: f32) -> Vec<Point> {
+    let tolerance = tolerance.max(1e-3);
+    let mut points = vec![contour.start];
+    let mut current = contour.start;
+    for segment in &contour.segments {
+        match *segment {
+            PathSegment::Line(to) => {
+                points.push(to);
+                current = to;
+            }
+            PathSegment::Cubic(c1, c2, to) => {
+                // The flattening error of n uniform steps is bounded by
+                // 3/4 of the largest second difference over n^2.
+                let dd = length(sub(add(current, c2), scale(c1, 2.0)))
+                    .max(length(sub(add(c1, to), scale(c2, 2.0))));
+                let steps = ((0.75 * dd / tolerance).sqrt().ceil() as usize).max(1);
+                for step in 1..=steps {
+                    points.push(cubic_at(current, c1, c2, to, step as f32 / steps as f32));
+                }
+                current = to;
+            }
+        }
+    }
+    points
+}
+
+/// Replaces each unit-length edge between opposite turns (a stair step) by
+/// its midpoint, so pixel staircases become straight diagonals.
+fn straighten_steps(polygon: Vec<Point>) -> Vec<Point> {
+    let n = polygon.len();
+    if n < 5 {
+        return polygon;
+    }
+    let turn = |i: usize| {
+        let prev = polygon[(i + n - 1) % n];
+        let next = polygon[(i + 1) % n];
+        cross(sub(polygon[i], prev), sub(next, polygon[i])).signum()
+    };
+
+    let mut out = Vec::with_capacity(n);
+    let mut consumed = vec![false; n];
+    for i in 0..n {
+        let j = (i + 1) % n;
+        if consumed[i] {
+            continue;
+        }
+        let is_step =
+            !consumed[j] && length(sub(polygon[j], polygon[i])) <= 1.0 && turn(i) * turn(j) < 0.0;
+        if is_step {
+            out.push(scale(add(polygon[i], polygon[j]), 0.5));
+            consumed[i] = true;
+            consumed[j] = true;
+        } else {
+            out.push(polygon[i]);
+        }
+    }
+    // The wrap-around edge can consume the first vertex after it was emitted.
+    if consumed[0] && out.first() == Some(&polygon[0]) {
+        out.remove(0);
+    }
+
+    if out.len() < 3 { polygon } else { out }
+}
+
+fn fit_contour(vertices: &[Point], alpha_max: f32) -> BezierContour {
+    let n = vertices.len();
+    let mid = |i: usize| scale(add(vertices[i], vertices[(i + 1) % n]), 0.5);
+    let mut segments = Vec::with_capacity(n);
+    for j in 0..n {
+        let i = (j + n - 1) % n;
+        let k = (j + 1) % n;
+        let (vi, vj, vk) = (vertices[i], vertices[j], vertices[k]);
+        let end = mid(j);
+
+        let denom = ddenom(vi, vk);
+        let alpha = if denom != 0.0 {
+            let dd = (dpara(vi, vj, vk) / denom).abs();
+            let alpha = if dd > 1.0 { 1.0 - 1.0 / dd } else { 0.0 };
+            alpha / 0.75
+        } else {
+            4.0 / 3.0
+        };
+
+        if alpha >= alpha_max {
+            segments.push(PathSegment::Line(vj));
+            segments.push(PathSegment::Line(end));
+        } else {
+            let alpha = alpha.clamp(0.55, 1.0);
+            let c1 = lerp(vi, vj, 0.5 + 0.5 * alpha);
+            let c2 = lerp(vk, vj, 0.5 + 0.5 * alpha);
+            segments.push(PathSegment::Cubic(c1, c2, end));
+        }
+    }
+    BezierContour {
+        start: mid(n - 1),
+        segments,
+    }
+}
+
+/// Twice the signed area of the triangle `p0 p1 p2`.
+fn dpara(p0: Point, p1: Point, p2: Point) -> f32 {
+    cross(sub(p1, p0), sub(p2, p0))
+}
+
+/// Potrace's normalizer for `dpara`, measured along the L-infinity
+/// orthogonal of `p0 p2`.
+fn ddenom(p0: Point, p2: Point) -> f32 {
+    let (dx, dy) = sub(p2, p0);
+    let r = (-sign(dy), sign(dx));
+    r.1 * dx - r.0 * dy
+}
+
+fn sign(v: f32) -> f32 {
+    if v > 0.0 {
+        1.0
+    } else if v < 0.0 {
+        -1.0
+    } else {
+        0.0
+    }
+}
+
+fn cubic_at(p0: Point, c1: Point, c2: Point, p3: Point, t: f32) -> Point {
+    let u = 1.0 - t;
+    let a = u * u * u;
+    let b = 3.0 * u * u * t;
+    let c = 3.0 * u * t * t;
+    let d = t * t * t;
+    (
+        a * p0.0 + b * c1.0 + c * c2.0 + d * p3.0,
+        a * p0.1 + b * c1.1 + c * c2.1 + d * p3.1,
+    )
+}
+
+fn lerp(a: Point, b: Point, t: f32) -> Point {
+    add(a, scale(sub(b, a), t))
+}
+
+fn add(a: Point, b: Point) -> Point {
+    (a.0 + b.0, a.1 + b.1)
+}
+
+fn sub(a: Point, b: Point) -> Point {
+    (a.0 - b.0, a.1 - b.1)
+}
+
+fn scale(a: Point, s: f32) -> Point {
+    (a.0 * s, a.1 * s)
+}
+
+fn cross(a: Point, b: Point) -> f32 {
+    a.0 * b.1 - a.1 * b.0
+}
+
+fn length(a: Point) -> f32 {
+    (a.0 * a.0 + a.1 * a.1).sqrt()
+}
@@ -0,0 +1,81 @@
+use commons::assignment::{AssignmentMethod, assign, assign_points, total_distance};
+use commons::sampling::SplitMix64;
+
+fn random_points(count: usize, seed: u64) -> Vec<(f32, f32)> {
+    let mut rng = SplitMix64::new(seed);
+    (0..count)
+        .map(|_| (rng.next_f32() * 800.0, rng.next_f32() * 600.0))
+        .collect()
+}
+
+const METHODS: [AssignmentMethod; 3] = [
+    AssignmentMethod::Hilbert,
+    AssignmentMethod::Refined {
+        passes: 4,
+        window: 8,
+    },
+    AssignmentMethod::Auction { epsilon: 0.01 },
+];
+
+#[test]
+fn equal_counts_give_a_permutation() {
+    let sources = random_points(300, 1);
+    let targets = random_points(300, 2);
+    for method in METHODS {
+        let mut assignment = assign(&sources, &targets, method);
+        assignment.sort_unstable();
+        assert_eq!(assignment, (0..300).collect::<Vec<_>>(), "{method:?}");
+    }
+}
+
+#[test]
+fn unequal_counts_cover_every_source() {
+    let sources = random_points(200, 3);
+    let targets = random_points(50, 4);
+    for method in METHODS {
+        let assignment = assign(&sources, &targets, method);
+        assert_eq!(assignment.len(), 200);
+        // Each target is shared by about the same number of sources.
+        let mut uses = [0usize; 50];
+        for &t in &assignment {
+            uses[t] += 1;
+        }
+        assert!(uses.iter().all(|&u| u == 4), "{method:?}: {uses:?}");
+    }
+    assert_eq!(assign_points(&targets, &sources, METHODS[1]).len(), 50);
+}
+
+#[test]
+fn translated_copy_maps_onto_itself() {
+    let sources = random_points(200, 5);
+    let targets: Vec<_> = sources.iter().rev().map(|&(x, y)| (x + 3.0, y)).collect();
+    let assignment = assign(
+        &sources,
+        &targets,
+        AssignmentMethod::Auction { epsilon: 0.01 },
+    );
+    for (i, &t) in assignment.iter().enumerate() {
+        assert_eq!(t, 199 - i);
+    }
+}
+
+#[test]
+fn higher_quality_travels_less() {
+    let sources = random_points(400, 6);
+    let targets = random_points(400, 7);
+    let cost: Vec<f32> = METHODS
+        .iter()
+        .map(|&method| total_distance(&sources, &targets, &assign(&sources, &targets, method)))
+        .collect();
+    let identity: Vec<usize> = (0..400).collect();
+    let naive = total_distance(&sources, &targets, &identity);
+    assert!(cost[0] < naive, "{cost:?} vs {naive}");
+    assert!(cost[1] <= cost[0], "{cost:?}");
+    assert!(cost[2] <= cost[1], "{cost:?}");
+}
+
+#[test]
+fn empty_inputs() {
+    assert!(assign(&[], &[(1.0, 1.0)], AssignmentMethod::default()).is_empty());
+    assert!(assign(&[(1.0, 1.0)], &[], AssignmentMethod::default()).is_empty());
+}
@@ -0,0 +1,36 @@
+use commons::{Charset, HAXOR_FONT, load_bdf};
+
+#[test]
+fn haxor_declares_latin2() {
+    let font = load_bdf(HAXOR_FONT).unwrap();
+    assert_eq!(font.charset(), Charset::Iso8859(2));
+}
+
+#[test]
+fn round_trips_through_unicode() {
+    let cases = [
+        (Charset::Iso8859(1), 0xE9, 'é'),
+        (Charset::Iso8859(2), 0xA3, 'Ł'),
+        (Charset::Iso8859(5), 0xE0, 'р'),
+        (Charset::Iso8859(15), 0xA4, '€'),
+        (Charset::Koi8R, 0xC1, 'а'),
+        (Charset::Cp437, 0x01, '☺'),
+        (Charset::Cp437, 0xDB, '█'),
+        (Charset::Unicode, 0x4E00, '一'),
+    ];
+    for (charset, code, c) in cases {
+        assert_eq!(charset.to_unicode(code), Some(c), "{charset:?} {code:#x}");
+        assert_eq!(charset.from_unicode(c), Some(code), "{charset:?} {c}");
+    }
+}
+
+#[test]
+fn unassigned_positions() {
+    assert_eq!(Charset::Iso8859(3).to_unicode(0xA5), None);
+    assert_eq!(Charset::Iso8859(2).from_unicode('€'), None);
+    assert_eq!(Charset::from_xlfd("ISO8859", "12"), None);
+    assert_eq!(
+        Charset::from_xlfd("\"ISO10646\"", "1"),
+        Some(Charset::Unicode)
+    );
+}
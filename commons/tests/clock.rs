@@ -0,0 +1,61 @@
+use commons::clock::{Clock, Source};
+
+#[test]
+fn fixed_clocks_step_exactly() {
+    let mut clock = Clock::fixed(1.0 / 60.0);
+    for _ in 0..3600 {
+        clock.tick();
+    }
+    assert_eq!(clock.dt(), 1.0 / 60.0);
+    assert!((clock.time() - 60.0).abs() < 1e-4);
+}
+
+#[test]
+fn manual_clocks_move_only_when_advanced() {
+    let mut clock = Clock::manual();
+    assert_eq!(clock.tick(), 0.0);
+    assert_eq!(clock.advance(0.25), 0.25);
+    assert_eq!(clock.time(), 0.25);
+    assert_eq!(clock.tick(), 0.0);
+    assert_eq!(clock.time(), 0.25);
+}
+
+#[test]
+fn real_time_clocks_start_at_zero_and_follow_the_wall_clock() {
+    let mut clock = Clock::real_time();
+    assert_eq!(clock.tick(), 0.0);
+    std::thread::sleep(std::time::Duration::from_millis(20));
+    assert!(clock.tick() >= 0.02);
+    assert!(matches!(
+        clock.source,
+        Source::RealTime { last_tick: Some(_) }
+    ));
+}
+
+#[test]
+fn speed_scales_and_pause_stops_time() {
+    let mut clock = Clock::fixed(0.5);
+    clock.set_speed(2.0);
+    assert_eq!(clock.tick(), 1.0);
+    clock.toggle_pause();
+    assert_eq!(clock.tick(), 0.0);
+    assert_eq!(clock.time(), 1.0);
+    clock.set_paused(false);
+    clock.set_speed(-1.0);
+    assert_eq!(clock.speed(), 0.0);
+    assert_eq!(clock.tick(), 0.0);
+}
+
+#[test]
+fn seeking_jumps_without_a_long_tick() {
+    let mut clock = Clock::fixed(0.5);
+    clock.tick();
+    assert_eq!(clock.seeks(), 0);
+    clock.seek(10.0);
+    assert_eq!(clock.seeks(), 1);
+    assert_eq!(clock.time(), 10.0);
+    assert_eq!(clock.tick(), 0.5);
+    assert_eq!(clock.time(), 10.5);
+    clock.seek(-3.0);
+    assert_eq!(clock.time(), 0.0);
+}
@@ -0,0 +1,43 @@
+#![allow(dead_code)]
+
+use commons::{BDFFont, BDFGlyph, GlyphSet, HAXOR_FONT, load_bdf};
+
+pub fn haxor_font() -> BDFFont {
+    load_bdf(HAXOR_FONT).unwrap()
+}
+
+pub fn haxor() -> GlyphSet {
+    GlyphSet::from_font(&haxor_font())
+}
+
+/// A lone glyph with the given `BBX` fields and hex bitmap rows.
+pub fn glyph(bbx: &str, rows: &[&str]) -> BDFGlyph {
+    let src = format!(
+        "STARTFONT 2.1\nCHARS 1\nSTARTCHAR test\nENCODING 65\nBBX {bbx}\nBITMAP\n{}\nENDCHAR\nENDFONT\n",
+        rows.join("\n")
+    );
+    load_bdf(&src).unwrap().glyphs.remove(0)
+}
+
+/// Shoelace area of a closed polygon; positive when counter-clockwise in
+/// y-up space.
+pub fn signed_area(polygon: &[(f32, f32)]) -> f32 {
+    polygon
+        .windows(2)
+        .map(|w| w[0].0 * w[1].1 - w[1].0 * w[0].1)
+        .sum::<f32>()
+        / 2.0
+}
+
+/// Whether `point` lies inside the closed `polygon`, by ray casting.
+pub fn contains(polygon: &[(f32, f32)], (x, y): (f32, f32)) -> bool {
+    polygon
+        .windows(2)
+        .filter(|w| {
+            let ((x0, y0), (x1, y1)) = (w[0], w[1]);
+            (y0 > y) != (y1 > y) && x < x0 + (y - y0) * (x1 - x0) / (y1 - y0)
+        })
+        .count()
+        % 2
+        == 1
+}
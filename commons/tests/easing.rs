@@ -0,0 +1,104 @@
+use commons::easing::{Curve, Easing};
+
+fn close(a: f32, b: f32) -> bool {
+    (a - b).abs() < 1e-4
+}
+
+#[test]
+fn easings_fix_their_endpoints_and_clamp() {
+    let extra = [
+        Easing::EASE,
+        Easing::CubicBezier(0.68, -0.6, 0.32, 1.6),
+        Easing::Steps(3),
+    ];
+    for easing in Easing::named().chain(extra) {
+        assert!(close(easing.apply(0.0), 0.0), "{easing}");
+        assert!(close(easing.apply(1.0), 1.0), "{easing}");
+        assert!(close(easing.apply(-1.0), 0.0), "{easing}");
+        assert!(close(easing.apply(2.0), 1.0), "{easing}");
+    }
+}
+
+#[test]
+fn in_out_pairs_are_symmetric() {
+    for easing in Easing::named() {
+        let mirrored = match easing {
+            Easing::In(c) => Easing::Out(c),
+            Easing::Out(c) => Easing::In(c),
+            other => other,
+        };
+        for i in 0..=20 {
+            let t = i as f32 / 20.0;
+            assert!(
+                close(easing.apply(t), 1.0 - mirrored.apply(1.0 - t)),
+                "{easing} at {t}"
+            );
+        }
+    }
+}
+
+#[test]
+fn matches_reference_values() {
+    assert!(close(Easing::In(Curve::Quad).apply(0.5), 0.25));
+    assert!(close(Easing::Out(Curve::Cubic).apply(0.5), 0.875));
+    assert!(close(Easing::InOut(Curve::Quart).apply(0.25), 0.03125));
+    assert!(close(Easing::In(Curve::Quint).apply(0.5), 0.03125));
+    assert!(close(Easing::InOut(Curve::Sine).apply(0.5), 0.5));
+    assert!(close(Easing::In(Curve::Expo).apply(0.5), 0.03125));
+    assert!(close(Easing::Out(Curve::Circ).apply(0.5), 0.75f32.sqrt()));
+    assert!(close(Easing::Out(Curve::Bounce).apply(0.5), 0.765625));
+    // Back dips below 0 and elastic overshoots 1.
+    assert!(close(Easing::In(Curve::Back).apply(0.5), -0.0876975));
+    assert!(Easing::Out(Curve::Elastic).apply(0.1) > 1.0);
+    assert!(Easing::InOut(Curve::Back).apply(0.1) < 0.0);
+    assert!(Easing::InOut(Curve::Elastic).apply(0.6) > 1.0);
+    // Bounce-out touches 1 between hops.
+    assert!(close(Easing::Out(Curve::Bounce).apply(1.0 / 2.75), 1.0));
+}
+
+#[test]
+fn cubic_bezier_follows_css() {
+    // The CSS `ease` keyword at a few points.
+    assert!(close(Easing::EASE.apply(0.25), 0.4085));
+    assert!(close(Easing::EASE.apply(0.5), 0.8024));
+    // Control points on the diagonal give a straight line.
+    let line = Easing::CubicBezier(0.25, 0.25, 0.75, 0.75);
+    for i in 0..=10 {
+        let t = i as f32 / 10.0;
+        assert!(close(line.apply(t), t), "{t}");
+    }
+    // A flat start still resolves.
+    assert!(Easing::CubicBezier(0.0, 0.0, 1.0, 1.0).apply(0.5) > 0.49);
+}
+
+#[test]
+fn steps_jump_at_the_end_of_each_interval() {
+    let steps = Easing::Steps(4);
+    assert_eq!(steps.apply(0.2), 0.0);
+    assert_eq!(steps.apply(0.25), 0.25);
+    assert_eq!(steps.apply(0.99), 0.75);
+    assert_eq!(steps.apply(1.0), 1.0);
+}
+
+#[test]
+fn names_round_trip() {
+    let extra = [Easing::CubicBezier(0.4, 0.0, 0.2, 1.0), Easing::Steps(12)];
+    for easing in Easing::named().chain(extra) {
+        assert_eq!(easing.to_string().parse(), Ok(easing));
+    }
+    assert_eq!("Elastic-In-Out".parse(), Ok(Easing::InOut(Curve::Elastic)));
+    assert_eq!("ease-in-out".parse(), Ok(Easing::EASE_IN_OUT));
+    assert_eq!(
+        "cubic-bezier( 0.1 ,0.7, 1.0, 0.1 )".parse(),
+        Ok(Easing::CubicBezier(0.1, 0.7, 1.0, 0.1))
+    );
+    for bad in [
+        "wobbly",
+        "quad",
+        "steps(0)",
+        "steps(2, 3)",
+        "cubic-bezier(1, 2)",
+    ] {
+        assert!(bad.parse::<Easing>().is_err(), "{bad}");
+    }
+}
@@ -0,0 +1,30 @@
+use commons::{BDFPropertyValue, GlyphSet, HAXOR_FONT, load_bdf};
+
+#[test]
+fn lookup_by_char() {
+    let glyphs = GlyphSet::from_font(&load_bdf(HAXOR_FONT).unwrap());
+    let a = glyphs.get('A').unwrap();
+    assert_eq!(a.encoding, 65);
+    assert_eq!(a.advance, 9);
+    assert_eq!(a.bounds.width, 7);
+    assert!(glyphs.get('\u{4e00}').is_none());
+}
+
+#[test]
+fn falls_back_to_default_char() {
+    let mut font = load_bdf(HAXOR_FONT).unwrap();
+    font.properties
+        .set("DEFAULT_CHAR", BDFPropertyValue::Int('?' as i64));
+    let glyphs = GlyphSet::from_font(&font);
+    assert_eq!(glyphs.get('\u{4e00}').unwrap().encoding, '?' as u32);
+    assert!(glyphs.get_exact('\u{4e00}').is_none());
+}
+
+#[test]
+fn latin2_glyphs_map_to_unicode() {
+    let glyphs = GlyphSet::from_font(&load_bdf(HAXOR_FONT).unwrap());
+    // U+010C is 0xC8 in ISO-8859-2; in Latin-1 0xC8 would be U+00C8.
+    assert_eq!(glyphs.get('Č').unwrap().encoding, 0xC8);
+    assert_eq!(glyphs.get('ł').unwrap().encoding, 0xB3);
+    assert!(glyphs.get_exact('È').is_none());
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use common::haxor;
+use commons::layout::{Align, LayoutOptions, VerticalAlign, layout_text};
+
+#[test]
+fn wraps_at_word_boundaries() {
+    // Haxor is monospaced with a 9px advance: 10 glyphs fit in 90px.
+    let options = LayoutOptions {
+        wrap_width: Some(90.0),
+        ..Default::default()
+    };
+    let layout = layout_text("I miss you so much", &haxor(), (400.0, 400.0), &options);
+    assert_eq!(layout.line_count, 2);
+    let second_line: String = layout
+        .glyphs
+        .iter()
+        .filter(|g| g.line == 1)
+        .map(|g| g.ch)
+        .collect();
+    assert_eq!(second_line, "somuch");
+    assert_eq!(layout.bounds.height, 2.0 * 19.0);
+}
+
+#[test]
+fn breaks_words_longer_than_a_line() {
+    let options = LayoutOptions {
+        wrap_width: Some(45.0),
+        ..Default::default()
+    };
+    let layout = layout_text("appreciation", &haxor(), (400.0, 400.0), &options);
+    assert_eq!(layout.line_count, 3);
+    assert_eq!(layout.glyphs.len(), 12);
+}
+
+#[test]
+fn centers_in_box() {
+    let options = LayoutOptions {
+        align: Align::Center,
+        vertical_align: VerticalAlign::Middle,
+        scale: 2.0,
+        ..Default::default()
+    };
+    let layout = layout_text("My fault.", &haxor(), (200.0, 100.0), &options);
+    let bounds = layout.bounds;
+    assert_eq!(bounds.width, 9.0 * 9.0 * 2.0);
+    assert_eq!(bounds.x, (200.0 - bounds.width) / 2.0);
+    assert_eq!(bounds.y, (100.0 - 38.0) / 2.0);
+}
+
+#[test]
+fn justify_fills_all_but_last_line() {
+    let options = LayoutOptions {
+        wrap_width: Some(100.0),
+        align: Align::Justify,
+        ..Default::default()
+    };
+    let layout = layout_text("a b c d e f g h i", &haxor(), (100.0, 100.0), &options);
+    let first_line: Vec<_> = layout.glyphs.iter().filter(|g| g.line == 0).collect();
+    let last = first_line.last().unwrap();
+    assert_eq!(last.x + 9.0, 100.0);
+    let final_line = layout.line_count - 1;
+    let tail: Vec<_> = layout
+        .glyphs
+        .iter()
+        .filter(|g| g.line == final_line)
+        .collect();
+    assert_eq!(tail[0].x, 0.0);
+    assert_eq!(tail[1].x, 18.0);
+}
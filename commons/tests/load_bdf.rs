@@ -0,0 +1,225 @@
+use commons::{BDFPropertyValue, BdfError, bdf_glyph_runs, bdf_to_rects, load_bdf};
+
+fn font_with_glyph(glyph: &str) -> String {
+    format!(
+        "STARTFONT 2.1\n\
+         FONT -test-Test-Medium-R-Normal--8-80-75-75-C-80-ISO10646-1\n\
+         SIZE 8 75 75\n\
+         FONTBOUNDINGBOX 8 8 0 -1\n\
+         CHARS 1\n\
+         {glyph}\n\
+         ENDFONT\n"
+    )
+}
+
+#[test]
+fn unencoded_glyph() {
+    let font = load_bdf(&font_with_glyph(
+        "STARTCHAR orphan\nENCODING -1\nSWIDTH 500 0\nDWIDTH 8 0\nBBX 1 1 0 0\nBITMAP\n80\nENDCHAR",
+    ))
+    .unwrap();
+    assert_eq!(font.glyphs[0].encoding, None);
+    assert_eq!(font.glyphs[0].alt_encoding, None);
+}
+
+#[test]
+fn unencoded_glyph_with_alternate_index() {
+    let font = load_bdf(&font_with_glyph(
+        "STARTCHAR orphan\nENCODING -1 200\nSWIDTH 500 0\nDWIDTH 8 0\nBBX 1 1 0 0\nBITMAP\n80\nENDCHAR",
+    ))
+    .unwrap();
+    assert_eq!(font.glyphs[0].encoding, None);
+    assert_eq!(font.glyphs[0].alt_encoding, Some(200));
+}
+
+#[test]
+fn negative_advance_widths() {
+    let font = load_bdf(&font_with_glyph(
+        "STARTCHAR rtl\nENCODING 1488\nSWIDTH -500 0\nDWIDTH -8 0\nBBX 1 1 0 0\nBITMAP\n80\nENDCHAR",
+    ))
+    .unwrap();
+    let glyph = &font.glyphs[0];
+    assert_eq!(glyph.encoding, Some(1488));
+    assert_eq!(glyph.swidth, (-500, 0));
+    assert_eq!(glyph.dwidth, (-8, 0));
+}
+
+#[test]
+fn vertical_metrics() {
+    let font = load_bdf(&font_with_glyph(
+        "STARTCHAR vert\nENCODING 12354\nSWIDTH 1000 0\nDWIDTH 16 0\n\
+         SWIDTH1 0 -1000\nDWIDTH1 0 -16\nVVECTOR 8 14\nBBX 1 1 0 0\nBITMAP\n80\nENDCHAR",
+    ))
+    .unwrap();
+    let glyph = &font.glyphs[0];
+    assert_eq!(glyph.swidth1, Some((0, -1000)));
+    assert_eq!(glyph.dwidth1, Some((0, -16)));
+    assert_eq!(glyph.vvector, Some((8, 14)));
+}
+
+#[test]
+fn invalid_negative_encoding() {
+    let err = load_bdf(&font_with_glyph(
+        "STARTCHAR bad\nENCODING -2\nBBX 1 1 0 0\nBITMAP\n80\nENDCHAR",
+    ))
+    .err()
+    .unwrap();
+    assert!(matches!(
+        err,
+        BdfError::MalformedField {
+            line: 7,
+            keyword: "ENCODING",
+            ..
+        }
+    ));
+}
+
+const PLAIN_GLYPH: &str = "STARTCHAR a\nENCODING 97\nBBX 1 1 0 0\nBITMAP\n80\nENDCHAR";
+
+#[test]
+fn truncated_file() {
+    let font = font_with_glyph(PLAIN_GLYPH);
+    let truncated = &font[..font.find("ENDCHAR").unwrap()];
+    assert_eq!(
+        load_bdf(truncated).err(),
+        Some(BdfError::UnexpectedEof {
+            line: 10,
+            expected: "ENDCHAR"
+        })
+    );
+    let truncated = &font[..font.find("ENDFONT").unwrap()];
+    assert_eq!(
+        load_bdf(truncated).err(),
+        Some(BdfError::UnexpectedEof {
+            line: 11,
+            expected: "ENDFONT"
+        })
+    );
+}
+
+#[test]
+fn short_size_and_bbx() {
+    let font = font_with_glyph(PLAIN_GLYPH).replace("SIZE 8 75 75", "SIZE 8 75");
+    assert_eq!(
+        load_bdf(&font).err(),
+        Some(BdfError::MalformedField {
+            line: 3,
+            keyword: "SIZE",
+            text: "SIZE 8 75".into()
+        })
+    );
+    let font = font_with_glyph(PLAIN_GLYPH).replace("BBX 1 1 0 0", "BBX 1 1 0");
+    assert_eq!(
+        load_bdf(&font).err(),
+        Some(BdfError::MalformedField {
+            line: 8,
+            keyword: "BBX",
+            text: "BBX 1 1 0".into()
+        })
+    );
+}
+
+#[test]
+fn non_hex_bitmap_row() {
+    let font = font_with_glyph(PLAIN_GLYPH).replace("\n80\n", "\n8G\n");
+    assert_eq!(
+        load_bdf(&font).err(),
+        Some(BdfError::BadBitmapRow {
+            line: 10,
+            text: "8G".into()
+        })
+    );
+}
+
+#[test]
+fn wrong_chars_count() {
+    let font = font_with_glyph(PLAIN_GLYPH).replace("CHARS 1", "CHARS 2");
+    assert_eq!(
+        load_bdf(&font).err(),
+        Some(BdfError::CharsMismatch {
+            line: 5,
+            declared: 2,
+            found: 1
+        })
+    );
+}
+
+#[test]
+fn three_byte_rows() {
+    let font = load_bdf(&font_with_glyph(
+        "STARTCHAR wide\nENCODING 87\nBBX 24 2 0 0\nBITMAP\n0000F0\n000001\nENDCHAR",
+    ))
+    .unwrap();
+    let glyph = &font.glyphs[0];
+    assert_eq!(glyph.row_bytes(), 3);
+    assert_eq!(glyph.row(1), [0x00, 0x00, 0x01]);
+    assert!(glyph.pixel(16, 0) && glyph.pixel(19, 0) && glyph.pixel(23, 1));
+    assert!(!glyph.pixel(20, 0) && !glyph.pixel(23, 0) && !glyph.pixel(24, 1));
+
+    let rects = bdf_to_rects(glyph);
+    assert_eq!(rects.len(), 5);
+    assert_eq!(rects[0][0], (16.0, 1.0));
+    assert_eq!(rects[4][0], (23.0, 0.0));
+
+    assert_eq!(
+        bdf_glyph_runs(glyph, 2),
+        vec![
+            vec![(16.0, 0.0), (20.0, 0.0)],
+            vec![(23.0, 1.0), (24.0, 1.0)]
+        ]
+    );
+}
+
+#[test]
+fn properties() {
+    let font = font_with_glyph(PLAIN_GLYPH).replace(
+        "CHARS 1",
+        "STARTPROPERTIES 5\n\
+         FONT_ASCENT 14\n\
+         COPYRIGHT \"the \"\"Haxor\"\" authors\"\n\
+         _GBDFED_INFO \"Edited with gbdfed 1.6.\"\n\
+         X_HAXOR_STYLE \"narrow\"\n\
+         FONT_DESCENT -3\n\
+         ENDPROPERTIES\n\
+         CHARS 1",
+    );
+    let mut font = load_bdf(&font).unwrap();
+    let props = &mut font.properties;
+    assert_eq!(props.get_int("FONT_ASCENT"), Some(14));
+    assert_eq!(props.get_int("FONT_DESCENT"), Some(-3));
+    assert_eq!(props.get_str("FONT_ASCENT"), None);
+    assert_eq!(props.get_str("COPYRIGHT"), Some("the \"Haxor\" authors"));
+    assert_eq!(props.get_int("COPYRIGHT"), None);
+    assert_eq!(
+        props.get_str("_GBDFED_INFO"),
+        Some("Edited with gbdfed 1.6.")
+    );
+    assert_eq!(props.get_str("X_HAXOR_STYLE"), Some("narrow"));
+    assert_eq!(props.get_str("FOUNDRY"), None);
+
+    props.set("FONT_ASCENT", BDFPropertyValue::Int(15));
+    props.set("FOUNDRY", BDFPropertyValue::Str("Test".into()));
+    assert_eq!(
+        props.remove("FONT_DESCENT"),
+        Some(BDFPropertyValue::Int(-3))
+    );
+    assert_eq!(props.remove("FONT_DESCENT"), None);
+    let names: Vec<&str> = props
+        .entries
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect();
+    assert_eq!(
+        names,
+        [
+            "FONT_ASCENT",
+            "COPYRIGHT",
+            "_GBDFED_INFO",
+            "X_HAXOR_STYLE",
+            "FOUNDRY"
+        ]
+    );
+
+    let reloaded = load_bdf(&font.to_bdf_string()).unwrap();
+    assert_eq!(reloaded.properties, font.properties);
+}
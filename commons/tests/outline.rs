@@ -0,0 +1,51 @@
+mod common;
+
+use common::{glyph, haxor_font, signed_area};
+use commons::bdf_to_outlines;
+
+#[test]
+fn solid_block_is_one_rectangle() {
+    let outlines = bdf_to_outlines(&glyph("3 2 1 -1", &["E0", "E0"]));
+    assert_eq!(outlines.len(), 1);
+    assert_eq!(
+        outlines[0],
+        vec![
+            (1.0, -1.0),
+            (4.0, -1.0),
+            (4.0, 1.0),
+            (1.0, 1.0),
+            (1.0, -1.0)
+        ]
+    );
+    assert_eq!(signed_area(&outlines[0]), 6.0);
+}
+
+#[test]
+fn ring_has_clockwise_hole() {
+    let outlines = bdf_to_outlines(&glyph("3 3 0 0", &["E0", "A0", "E0"]));
+    assert_eq!(outlines.len(), 2);
+    let mut areas: Vec<f32> = outlines.iter().map(|p| signed_area(p)).collect();
+    areas.sort_by(f32::total_cmp);
+    assert_eq!(areas, vec![-1.0, 9.0]);
+}
+
+#[test]
+fn diagonal_pixels_stay_separate() {
+    let outlines = bdf_to_outlines(&glyph("2 2 0 0", &["40", "80"]));
+    assert_eq!(outlines.len(), 2);
+    assert!(outlines.iter().all(|p| p.len() == 5));
+}
+
+#[test]
+fn haxor_outline_area_matches_pixel_count() {
+    let font = haxor_font();
+    for glyph in &font.glyphs {
+        let (w, h, _, _) = glyph.bbx;
+        let pixels = (0..h)
+            .flat_map(|row| (0..w).map(move |col| (col, row)))
+            .filter(|&(col, row)| glyph.pixel(col, row))
+            .count();
+        let area: f32 = bdf_to_outlines(glyph).iter().map(|p| signed_area(p)).sum();
+        assert_eq!(area, pixels as f32, "{}", glyph.startchar);
+    }
+}
@@ -0,0 +1,96 @@
+use commons::physics::{
+    Attractor, FixedTimestep, Particle, PhysicsParams, curl_noise, gradient_noise, step_particles,
+};
+
+fn simulate(frame_dt: f32, seconds: f32, params: &PhysicsParams) -> Particle {
+    let mut particles = [Particle::at(0.0, 0.0)];
+    let mut clock = FixedTimestep::new(1.0 / 120.0);
+    let mut time = 0.0;
+    for _ in 0..(seconds / frame_dt).round() as usize {
+        for _ in 0..clock.advance(frame_dt) {
+            step_particles(&mut particles, |_| (100.0, 50.0), params, time, clock.step);
+            time += clock.step;
+        }
+    }
+    particles[0]
+}
+
+#[test]
+fn fixed_timestep_counts_whole_steps() {
+    let mut clock = FixedTimestep::new(0.01);
+    assert_eq!(clock.advance(0.025), 2);
+    assert!((clock.alpha() - 0.5).abs() < 1e-3);
+    assert_eq!(clock.advance(0.005), 1);
+    assert_eq!(clock.advance(10.0), clock.max_steps);
+    assert_eq!(clock.advance(0.0), 0);
+}
+
+#[test]
+fn fixed_timestep_counts_steps_from_time_zero() {
+    let mut clock = FixedTimestep::new(0.25);
+    assert_eq!(clock.advance_to(0.6), 2);
+    assert_eq!(clock.advance_to(1.1), 2);
+    assert_eq!(clock.steps(), 4);
+    assert_eq!(clock.advance_to(0.5), 0);
+    assert_eq!(clock.advance_to(10.0), clock.max_steps);
+    assert_eq!(clock.steps(), 40);
+    clock.seek(2.0);
+    assert_eq!(clock.advance_to(2.3), 1);
+    assert_eq!(clock.steps(), 9);
+}
+
+#[test]
+fn result_does_not_depend_on_frame_rate() {
+    let params = PhysicsParams {
+        curl_strength: 200.0,
+        noise_strength: 50.0,
+        ..Default::default()
+    };
+    let at_30 = simulate(1.0 / 30.0, 1.5, &params);
+    let at_120 = simulate(1.0 / 120.0, 1.5, &params);
+    assert!((at_30.x - at_120.x).abs() < 1e-3, "{at_30:?} vs {at_120:?}");
+    assert!((at_30.y - at_120.y).abs() < 1e-3, "{at_30:?} vs {at_120:?}");
+}
+
+#[test]
+fn underdamped_spring_overshoots_then_settles() {
+    let params = PhysicsParams::default();
+    let mut particles = [Particle::at(0.0, 0.0)];
+    let mut max_x = 0.0f32;
+    for _ in 0..600 {
+        step_particles(&mut particles, |_| (100.0, 0.0), &params, 0.0, 1.0 / 120.0);
+        max_x = max_x.max(particles[0].x);
+    }
+    assert!(max_x > 100.5, "no overshoot: {max_x}");
+    assert!((particles[0].x - 100.0).abs() < 0.5);
+    assert!(particles[0].vx.abs() < 1.0);
+}
+
+#[test]
+fn repulsor_pushes_away() {
+    let params = PhysicsParams {
+        attractors: vec![Attractor {
+            x: 10.0,
+            y: 0.0,
+            strength: -5000.0,
+            radius: 50.0,
+        }],
+        ..Default::default()
+    };
+    let mut particles = [Particle::at(0.0, 0.0)];
+    step_particles(&mut particles, |_| (0.0, 0.0), &params, 0.0, 0.01);
+    assert!(particles[0].vx < 0.0);
+}
+
+#[test]
+fn noise_is_bounded_and_curl_is_divergence_free() {
+    let e = 1e-2;
+    for i in 0..200 {
+        let (x, y) = (i as f32 * 0.37, i as f32 * 0.73);
+        assert!(gradient_noise(x, y, 0).abs() <= 1.01);
+
+        let div = (curl_noise(x + e, y, 3).0 - curl_noise(x - e, y, 3).0) / (2.0 * e)
+            + (curl_noise(x, y + e, 3).1 - curl_noise(x, y - e, 3).1) / (2.0 * e);
+        assert!(div.abs() < 0.2, "divergence {div} at ({x}, {y})");
+    }
+}
@@ -0,0 +1,68 @@
+use commons::layout::{LayoutOptions, layout_text};
+use commons::sampling::{SampleMethod, SampleOptions, SampleRegion, sample_text};
+use commons::{GlyphSet, HAXOR_FONT, load_bdf};
+
+fn setup(text: &str) -> (GlyphSet, commons::layout::TextLayout) {
+    let glyphs = GlyphSet::from_font(&load_bdf(HAXOR_FONT).unwrap());
+    let options = LayoutOptions {
+        scale: 3.0,
+        ..Default::default()
+    };
+    let layout = layout_text(text, &glyphs, (2000.0, 200.0), &options);
+    (glyphs, layout)
+}
+
+#[test]
+fn returns_exactly_count_points() {
+    let (glyphs, layout) = setup("I miss you.");
+    for method in [
+        SampleMethod::Stratified,
+        SampleMethod::BlueNoise { candidates: 8 },
+    ] {
+        for region in [SampleRegion::Fill, SampleRegion::Outline] {
+            let options = SampleOptions {
+                region,
+                method,
+                seed: 7,
+            };
+            let points = sample_text(&layout, &glyphs, 3000, &options);
+            assert_eq!(points.len(), 3000);
+        }
+    }
+}
+
+#[test]
+fn same_seed_same_points() {
+    let (glyphs, layout) = setup("My fault.");
+    let options = SampleOptions {
+        method: SampleMethod::BlueNoise { candidates: 4 },
+        seed: 42,
+        ..Default::default()
+    };
+    let a = sample_text(&layout, &glyphs, 500, &options);
+    let b = sample_text(&layout, &glyphs, 500, &options);
+    assert_eq!(a, b);
+}
+
+#[test]
+fn fill_points_land_on_lit_pixels() {
+    let (glyphs, layout) = setup("Where you need to be.");
+    let points = sample_text(&layout, &glyphs, 2000, &SampleOptions::default());
+    for &(x, y) in &points {
+        let hit = layout.glyphs.iter().any(|placed| {
+            glyphs.get(placed.ch).unwrap().paths.iter().any(|run| {
+                let s = placed.scale;
+                let (x1, x2) = (placed.x + run[0].0 * s, placed.x + run[1].0 * s);
+                let y1 = placed.y + run[0].1 * s;
+                x >= x1 && x <= x2 && y >= y1 && y <= y1 + s
+            })
+        });
+        assert!(hit, "({x}, {y}) is not on a lit pixel");
+    }
+}
+
+#[test]
+fn empty_text_has_no_points() {
+    let (glyphs, layout) = setup("   ");
+    assert!(sample_text(&layout, &glyphs, 100, &SampleOptions::default()).is_empty());
+}
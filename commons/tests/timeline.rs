@@ -0,0 +1,101 @@
+use commons::easing::{Curve, Easing};
+use commons::timeline::{Timeline, Track};
+
+fn close(a: f32, b: f32) -> bool {
+    (a - b).abs() < 1e-5
+}
+
+#[test]
+fn track_holds_outside_its_keyframes() {
+    let track = Track::new(1.0).key(2.0, 3.0, Easing::Linear);
+    assert_eq!(track.sample(-1.0), 1.0);
+    assert_eq!(track.sample(1.0), 2.0);
+    assert_eq!(track.sample(5.0), 3.0);
+    assert_eq!(track.duration(), 2.0);
+}
+
+#[test]
+fn segments_use_the_easing_of_the_keyframe_they_reach() {
+    let track = Track::new(0.0).then(1.0, 1.0, Easing::Smoothstep).then(
+        1.0,
+        0.0,
+        Easing::InOut(Curve::Expo),
+    );
+    assert!(close(track.sample(0.25), Easing::Smoothstep.apply(0.25)));
+    assert!(close(
+        track.sample(1.25),
+        1.0 - Easing::InOut(Curve::Expo).apply(0.25)
+    ));
+}
+
+#[test]
+fn keyframes_sort_by_time_and_equal_times_cut() {
+    let track = Track::new(0.0)
+        .key(2.0, 10.0, Easing::Linear)
+        .key(1.0, 5.0, Easing::Linear)
+        .key(2.0, -10.0, Easing::Linear);
+    let times: Vec<f32> = track.keys().iter().map(|k| k.time).collect();
+    assert_eq!(times, [0.0, 1.0, 2.0, 2.0]);
+    assert_eq!(track.sample(1.5), 7.5);
+    assert_eq!(track.sample(2.0), -10.0);
+}
+
+#[test]
+fn tracks_interpolate_points_and_colors() {
+    let point = Track::new((0.0, 0.0)).then(2.0, (4.0, -2.0), Easing::Linear);
+    assert_eq!(point.sample(1.0), (2.0, -1.0));
+
+    let color =
+        Track::new([1.0, 0.0, 0.0, 1.0])
+            .hold(1.0)
+            .then(1.0, [0.0, 0.0, 1.0, 0.0], Easing::Linear);
+    assert_eq!(color.sample(0.5), [1.0, 0.0, 0.0, 1.0]);
+    assert_eq!(color.sample(1.5), [0.5, 0.0, 0.5, 0.5]);
+}
+
+#[test]
+fn looping_track_repeats() {
+    let track = Track::new(0.0)
+        .key(1.0, 0.0, Easing::Linear)
+        .key(3.0, 2.0, Easing::Linear)
+        .looping(true);
+    assert_eq!(track.sample(2.0), 1.0);
+    assert_eq!(track.sample(5.0), 1.0);
+    assert_eq!(track.sample(-1.0), 1.0);
+    assert_eq!(track.sample(4.0), 0.0);
+}
+
+#[test]
+fn timeline_sequences_and_overlaps_spans() {
+    let mut timeline = Timeline::new();
+    timeline.then(2.0, "a");
+    timeline.then(3.0, "b");
+    timeline.after(-1.0, 2.0, "c");
+    timeline.add(10.0, 1.0, "d");
+
+    let starts: Vec<f32> = timeline.spans().iter().map(|s| s.start).collect();
+    assert_eq!(starts, [0.0, 2.0, 4.0, 10.0]);
+    assert_eq!(timeline.duration(), 11.0);
+
+    let active: Vec<&str> = timeline.active(4.5).map(|a| *a.item).collect();
+    assert_eq!(active, ["b", "c"]);
+    let latest = timeline.at(4.5).unwrap();
+    assert_eq!(
+        (*latest.item, latest.local, latest.progress),
+        ("c", 0.5, 0.25)
+    );
+    assert_eq!(*timeline.at(2.0).unwrap().item, "b");
+    assert!(timeline.at(8.0).is_none());
+    assert!(timeline.at(11.0).is_none());
+}
+
+#[test]
+fn looping_timeline_wraps_queries() {
+    let mut timeline = Timeline::new().looping(true);
+    timeline.then(1.0, 0);
+    timeline.then(2.5, 1);
+    let at = timeline.at(4.0).unwrap();
+    assert_eq!((at.index, at.local), (0, 0.5));
+    assert_eq!(timeline.at(5.0).unwrap().index, 1);
+    assert_eq!(timeline.local_time(-0.5), 3.0);
+}
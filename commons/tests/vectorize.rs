@@ -0,0 +1,90 @@
+mod common;
+
+use common::{contains, glyph, haxor_font, signed_area};
+use commons::{PathSegment, VectorizeOptions, flatten_contour, vectorize_glyph};
+
+#[test]
+fn zero_threshold_keeps_every_corner() {
+    let options = VectorizeOptions {
+        corner_threshold: 0.0,
+        ..Default::default()
+    };
+    let contours = vectorize_glyph(&glyph("4 4 0 0", &["F0", "F0", "F0", "F0"]), &options);
+    assert_eq!(contours.len(), 1);
+    assert!(
+        contours[0]
+            .segments
+            .iter()
+            .all(|s| matches!(s, PathSegment::Line(_)))
+    );
+    let flat = flatten_contour(&contours[0], 0.1);
+    assert_eq!(signed_area(&flat), 16.0);
+}
+
+#[test]
+fn small_block_rounds_off() {
+    let contours = vectorize_glyph(&glyph("2 2 0 0", &["C0", "C0"]), &Default::default());
+    assert!(
+        contours[0]
+            .segments
+            .iter()
+            .all(|s| matches!(s, PathSegment::Cubic(..)))
+    );
+    let area = signed_area(&flatten_contour(&contours[0], 0.01));
+    assert!(area > 3.0 && area < 4.0, "{area}");
+}
+
+#[test]
+fn staircase_becomes_diagonal() {
+    let rows = ["80", "C0", "E0", "F0"];
+    let options = VectorizeOptions {
+        corner_threshold: 0.0,
+        ..Default::default()
+    };
+    let contours = vectorize_glyph(&glyph("4 4 0 0", &rows), &options);
+    let flat = flatten_contour(&contours[0], 0.1);
+    // Every point lies on the triangle's legs or on the smoothed hypotenuse.
+    for &(x, y) in &flat {
+        assert!(
+            x.abs() < 1e-4 || y.abs() < 1e-4 || (x + y - 4.5).abs() <= 0.5,
+            "{x},{y}"
+        );
+    }
+    let straight = vectorize_glyph(
+        &glyph("4 4 0 0", &rows),
+        &VectorizeOptions {
+            smooth_steps: false,
+            ..options
+        },
+    );
+    assert!(flat.len() < flatten_contour(&straight[0], 0.1).len());
+}
+
+#[test]
+fn haxor_contours_keep_their_winding() {
+    let mut holes = 0;
+    for glyph in &haxor_font().glyphs {
+        let contours: Vec<Vec<(f32, f32)>> = vectorize_glyph(glyph, &Default::default())
+            .iter()
+            .map(|contour| flatten_contour(contour, 0.05))
+            .collect();
+        for (i, flat) in contours.iter().enumerate() {
+            assert_eq!(flat.first(), flat.last());
+            // Outer contours wind one way and the holes inside them the
+            // other, so nesting depth decides the sign.
+            let depth = contours
+                .iter()
+                .enumerate()
+                .filter(|&(j, other)| j != i && contains(other, flat[0]))
+                .count();
+            let area = signed_area(flat);
+            if depth % 2 == 0 {
+                assert!(area > 0.1, "{} outer: {area}", glyph.startchar);
+            } else {
+                assert!(area < -0.1, "{} hole: {area}", glyph.startchar);
+                holes += 1;
+            }
+        }
+    }
+    assert!(holes > 0);
+}
@@ -0,0 +1,52 @@
+use commons::{HAXOR_FONT, load_bdf};
+
+#[test]
+fn haxor_round_trip() {
+    let font = load_bdf(HAXOR_FONT).unwrap();
+    let written = font.to_bdf_string();
+    assert_eq!(load_bdf(&written).unwrap(), font);
+}
+
+#[test]
+fn haxor_output_matches_source() {
+    let written = load_bdf(HAXOR_FONT).unwrap().to_bdf_string();
+    assert_eq!(written.trim_end(), HAXOR_FONT.trim_end());
+}
+
+#[test]
+fn quotes_are_escaped() {
+    let mut font = load_bdf(HAXOR_FONT).unwrap();
+    font.properties.set(
+        "COPYRIGHT",
+        commons::BDFPropertyValue::Str("the \"Haxor\" authors".to_string()),
+    );
+    let written = font.to_bdf_string();
+    assert!(written.contains("COPYRIGHT \"the \"\"Haxor\"\" authors\"\n"));
+    assert_eq!(load_bdf(&written).unwrap(), font);
+}
+
+#[test]
+fn subset_round_trip() {
+    let font = load_bdf(HAXOR_FONT).unwrap();
+    let subset = font.subset("I miss you.".chars());
+    let mut encodings: Vec<u32> = subset.glyphs.iter().filter_map(|g| g.encoding).collect();
+    encodings.sort();
+    assert_eq!(encodings, vec![32, 46, 73, 105, 109, 111, 115, 117, 121]);
+
+    let written = subset.to_bdf_string();
+    assert!(written.contains("CHARS 9\n"));
+    assert_eq!(load_bdf(&written).unwrap(), subset);
+}
+
+#[test]
+fn subset_ignores_out_of_range_default_char() {
+    let mut font = load_bdf(HAXOR_FONT).unwrap();
+    // Wraps around to 'A' if truncated to a u32.
+    font.properties.set(
+        "DEFAULT_CHAR",
+        commons::BDFPropertyValue::Int('A' as i64 - (1 << 32)),
+    );
+    let subset = font.subset("I".chars());
+    let encodings: Vec<u32> = subset.glyphs.iter().filter_map(|g| g.encoding).collect();
+    assert_eq!(encodings, vec![73]);
+}
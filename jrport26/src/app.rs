@@ -0,0 +1,174 @@
+use commons::clock::Clock;
+use pixels::{Pixels, SurfaceTexture};
+use std::sync::Arc;
+use std::time::Instant;
+use winit::{
+    application::ApplicationHandler,
+    event::WindowEvent,
+    event_loop::{ActiveEventLoop, EventLoop},
+    keyboard::{Key, NamedKey},
+    window::{Window, WindowId},
+};
+
+use crate::display;
+use crate::export;
+use crate::headless::Headless;
+use crate::options::Options;
+use crate::reel::Reel;
+
+/// Seconds the arrow keys seek by.
+const SEEK_STEP: f32 = 5.0;
+
+/// Plays the reel `make` builds, as `options` ask: in a window titled
+/// `title`, or offscreen (and exported) when they say `--headless`. `make`
+/// gets the device to build on, the target format and the target size.
+pub fn run<R, F>(
+    title: &str,
+    options: Options,
+    mut make: F,
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    R: Reel,
+    F: FnMut(&wgpu::Device, &wgpu::Queue, wgpu::TextureFormat, u32, u32) -> R,
+{
+    if options.headless {
+        let started = Instant::now();
+        let frames = export::run_headless(&options, |headless, width, height| {
+            make(
+                headless.device(),
+                headless.queue(),
+                Headless::FORMAT,
+                width,
+                height,
+            )
+        })?;
+        eprintln!(
+            "rendered {frames} frames in {:.1}s",
+            started.elapsed().as_secs_f32()
+        );
+        return Ok(());
+    }
+
+    let event_loop = EventLoop::new()?;
+    let mut app = App {
+        window: None,
+        pixels: None,
+        reel: None,
+        title: title.to_string(),
+        options,
+        clock: Clock::real_time(),
+        make,
+    };
+    event_loop.run_app(&mut app)?;
+    Ok(())
+}
+
+/// A window that plays a reel and takes the playback keys.
+struct App<R, F> {
+    window: Option<Arc<Window>>,
+    pixels: Option<Pixels<'static>>,
+    reel: Option<R>,
+    title: String,
+    options: Options,
+    clock: Clock,
+    make: F,
+}
+
+impl<R: Reel, F> App<R, F> {
+    /// Matches the surface, the pixel buffer and the reel to the window's
+    /// size, after it was resized or its scale factor changed.
+    fn resize(&mut self) {
+        let (Some(window), Some(pixels), Some(reel)) =
+            (&self.window, &mut self.pixels, &mut self.reel)
+        else {
+            return;
+        };
+        let size = window.inner_size();
+        // Minimized windows report a zero size; keep the last one until
+        // they're restored.
+        if size.width == 0 || size.height == 0 {
+            return;
+        }
+        pixels.resize_surface(size.width, size.height).unwrap();
+        pixels.resize_buffer(size.width, size.height).unwrap();
+        reel.resize(pixels.device(), pixels.queue(), size.width, size.height);
+    }
+}
+
+impl<R, F> ApplicationHandler for App<R, F>
+where
+    R: Reel,
+    F: FnMut(&wgpu::Device, &wgpu::Queue, wgpu::TextureFormat, u32, u32) -> R,
+{
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        if self.options.list_monitors {
+            print!("{}", display::describe_monitors(event_loop));
+            event_loop.exit();
+            return;
+        }
+        let window = Arc::new(
+            event_loop
+                .create_window(
+                    display::window_attributes(event_loop, &self.options).with_title(&self.title),
+                )
+                .unwrap(),
+        );
+
+        let size = window.inner_size();
+        let surface_texture = SurfaceTexture::new(size.width, size.height, Arc::clone(&window));
+        let pixels = Pixels::new(size.width, size.height, surface_texture).unwrap();
+
+        self.reel = Some((self.make)(
+            pixels.device(),
+            pixels.queue(),
+            pixels.render_texture_format(),
+            size.width,
+            size.height,
+        ));
+        self.pixels = Some(pixels);
+        self.window = Some(window);
+    }
+
+    fn window_event(&mut self, event_loop: &ActiveEventLoop, _: WindowId, event: WindowEvent) {
+        match event {
+            WindowEvent::CloseRequested => event_loop.exit(),
+            WindowEvent::Resized(_) | WindowEvent::ScaleFactorChanged { .. } => self.resize(),
+            WindowEvent::KeyboardInput { event, .. } => {
+                if !event.state.is_pressed() {
+                    return;
+                }
+                match event.logical_key {
+                    Key::Named(NamedKey::Escape) => event_loop.exit(),
+                    Key::Named(NamedKey::Space) => self.clock.toggle_pause(),
+                    Key::Named(NamedKey::ArrowLeft) => {
+                        self.clock.seek(self.clock.time() - SEEK_STEP)
+                    }
+                    Key::Named(NamedKey::ArrowRight) => {
+                        self.clock.seek(self.clock.time() + SEEK_STEP)
+                    }
+                    Key::Named(NamedKey::ArrowUp) => self.clock.set_speed(self.clock.speed() * 2.0),
+                    Key::Named(NamedKey::ArrowDown) => {
+                        self.clock.set_speed(self.clock.speed() / 2.0)
+                    }
+                    _ => {}
+                }
+            }
+            WindowEvent::RedrawRequested => {
+                self.clock.tick();
+
+                if let (Some(pixels), Some(reel)) = (&self.pixels, &mut self.reel) {
+                    reel.update(pixels.device(), pixels.queue(), &self.clock);
+                    pixels
+                        .render_with(|encoder, target, context| {
+                            context.scaling_renderer.render(encoder, target);
+                            reel.render(encoder, &context.queue, target);
+                            Ok(())
+                        })
+                        .unwrap();
+                }
+                self.window.as_ref().unwrap().request_redraw();
+            }
+            _ => {}
+        }
+    }
+}
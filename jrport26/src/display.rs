@@ -0,0 +1,164 @@
+use serde::Deserialize;
+use winit::dpi::{PhysicalPosition, PhysicalSize};
+use winit::event_loop::ActiveEventLoop;
+use winit::monitor::{MonitorHandle, VideoModeHandle};
+use winit::window::{Fullscreen, WindowAttributes};
+
+use crate::options::Options;
+
+/// Window size when running windowed without `--size`.
+const WINDOWED_SIZE: (u32, u32) = (1280, 720);
+
+/// A monitor, by its position in the system's list or by its name (such as
+/// `eDP-1` or `HDMI-1` on Linux).
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum MonitorChoice {
+    Index(usize),
+    Name(String),
+}
+
+impl MonitorChoice {
+    /// Position of the chosen monitor among `names`, listed in the
+    /// system's order.
+    pub fn find(&self, names: impl IntoIterator<Item = Option<String>>) -> Option<usize> {
+        let mut names = names.into_iter();
+        match self {
+            MonitorChoice::Index(index) => names.nth(*index).map(|_| *index),
+            MonitorChoice::Name(name) => names.position(|n| n.as_deref() == Some(name)),
+        }
+    }
+}
+
+impl std::str::FromStr for MonitorChoice {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.parse() {
+            Ok(index) => MonitorChoice::Index(index),
+            Err(_) => MonitorChoice::Name(s.to_string()),
+        })
+    }
+}
+
+impl std::fmt::Display for MonitorChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MonitorChoice::Index(index) => write!(f, "{index}"),
+            MonitorChoice::Name(name) => write!(f, "{name:?}"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WindowMode {
+    Windowed,
+    /// A borderless window covering the monitor, at its current resolution.
+    #[default]
+    Borderless,
+    /// Switches the monitor's resolution to the window's.
+    Exclusive,
+}
+
+impl std::str::FromStr for WindowMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "windowed" => Ok(WindowMode::Windowed),
+            "borderless" => Ok(WindowMode::Borderless),
+            "exclusive" => Ok(WindowMode::Exclusive),
+            _ => Err(format!("unknown window mode {s:?}")),
+        }
+    }
+}
+
+/// One line per monitor: index, name, resolution, refresh rate and scale.
+pub fn describe_monitors(event_loop: &ActiveEventLoop) -> String {
+    let primary = event_loop.primary_monitor();
+    let mut lines = String::new();
+    for (index, monitor) in event_loop.available_monitors().enumerate() {
+        let size = monitor.size();
+        lines += &format!(
+            "{index}: {}  {}x{}",
+            monitor.name().as_deref().unwrap_or("(unnamed)"),
+            size.width,
+            size.height
+        );
+        if let Some(mhz) = monitor.refresh_rate_millihertz() {
+            lines += &format!(" at {:.0} Hz", mhz as f32 / 1000.0);
+        }
+        lines += &format!(", scale {}", monitor.scale_factor());
+        if primary.as_ref() == Some(&monitor) {
+            lines += " (primary)";
+        }
+        lines += "\n";
+    }
+    lines
+}
+
+/// The monitor `choice` names, or the primary one when it's not given or
+/// not connected. Some platforms don't say which monitor is primary; the
+/// first one listed stands in for it there.
+pub fn pick_monitor(
+    event_loop: &ActiveEventLoop,
+    choice: Option<&MonitorChoice>,
+) -> Option<MonitorHandle> {
+    let monitors: Vec<MonitorHandle> = event_loop.available_monitors().collect();
+    if let Some(choice) = choice {
+        match choice.find(monitors.iter().map(|m| m.name())) {
+            Some(index) => return Some(monitors[index].clone()),
+            None => eprintln!("monitor {choice} not found, using the primary monitor"),
+        }
+    }
+    event_loop
+        .primary_monitor()
+        .or_else(|| monitors.into_iter().next())
+}
+
+/// Attributes for a reel window placed as `options` ask: on their monitor,
+/// in their mode, at their size. A windowed reel is centered on the monitor;
+/// an exclusive one switches to the closest video mode to `options.size`,
+/// or the monitor's largest without one.
+pub fn window_attributes(event_loop: &ActiveEventLoop, options: &Options) -> WindowAttributes {
+    let monitor = pick_monitor(event_loop, options.monitor.as_ref());
+    let attributes = WindowAttributes::default();
+    match options.mode.unwrap_or_default() {
+        WindowMode::Windowed => {
+            let (width, height) = options.size.unwrap_or(WINDOWED_SIZE);
+            let attributes = attributes.with_inner_size(PhysicalSize::new(width, height));
+            match monitor {
+                Some(monitor) => {
+                    let (origin, size) = (monitor.position(), monitor.size());
+                    attributes.with_position(PhysicalPosition::new(
+                        origin.x + (size.width as i32 - width as i32) / 2,
+                        origin.y + (size.height as i32 - height as i32) / 2,
+                    ))
+                }
+                None => attributes,
+            }
+        }
+        WindowMode::Borderless => attributes.with_fullscreen(Some(Fullscreen::Borderless(monitor))),
+        WindowMode::Exclusive => match monitor.as_ref().and_then(|m| video_mode(m, options.size)) {
+            Some(mode) => attributes.with_fullscreen(Some(Fullscreen::Exclusive(mode))),
+            None => {
+                eprintln!("no exclusive video mode available, using borderless fullscreen");
+                attributes.with_fullscreen(Some(Fullscreen::Borderless(monitor)))
+            }
+        },
+    }
+}
+
+/// The video mode nearest `size`, or the largest without one, breaking
+/// ties by refresh rate.
+fn video_mode(monitor: &MonitorHandle, size: Option<(u32, u32)>) -> Option<VideoModeHandle> {
+    monitor.video_modes().max_by_key(|mode| {
+        let PhysicalSize { width, height } = mode.size();
+        let fit = match size {
+            Some((w, h)) => -((width.abs_diff(w) + height.abs_diff(h)) as i64),
+            None => width as i64 * height as i64,
+        };
+        (fit, mode.refresh_rate_millihertz())
+    })
+}
@@ -0,0 +1,271 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, Command, ExitStatus, Stdio};
+
+use crate::headless::{Headless, HeadlessError};
+use crate::options::Options;
+use crate::reel::Reel;
+
+/// What an export writes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Numbered frames (`frame_00000.png`, ...) in a directory.
+    PngSequence,
+    /// A video encoded by a local `ffmpeg`, in whatever container the file
+    /// extension names (`.mp4`, `.webm`, ...).
+    Video,
+    Gif,
+    Apng,
+}
+
+impl ExportFormat {
+    /// Picks a format from `path`'s extension. Paths without a known one
+    /// are taken as the directory of a PNG sequence.
+    pub fn from_path(path: &Path) -> ExportFormat {
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        match extension.to_ascii_lowercase().as_str() {
+            "gif" => ExportFormat::Gif,
+            "apng" => ExportFormat::Apng,
+            "mp4" | "m4v" | "mov" | "mkv" | "webm" => ExportFormat::Video,
+            _ => ExportFormat::PngSequence,
+        }
+    }
+}
+
+impl std::str::FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "png" => Ok(ExportFormat::PngSequence),
+            "video" => Ok(ExportFormat::Video),
+            "gif" => Ok(ExportFormat::Gif),
+            "apng" => Ok(ExportFormat::Apng),
+            _ => Err(format!("unknown export format {s:?}")),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ExportError {
+    Headless(HeadlessError),
+    Io(std::io::Error),
+    Png(png::EncodingError),
+    Gif(gif::EncodingError),
+    /// `ffmpeg` could not be started, most likely because it isn't on the
+    /// `PATH`.
+    FfmpegMissing(std::io::Error),
+    FfmpegFailed(ExitStatus),
+    /// GIFs are at most 65535 pixels on a side.
+    TooLarge {
+        width: u32,
+        height: u32,
+    },
+}
+
+impl std::fmt::Display for ExportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportError::Headless(err) => err.fmt(f),
+            ExportError::Io(err) => write!(f, "cannot write export: {err}"),
+            ExportError::Png(err) => write!(f, "cannot encode PNG: {err}"),
+            ExportError::Gif(err) => write!(f, "cannot encode GIF: {err}"),
+            ExportError::FfmpegMissing(err) => write!(f, "cannot run ffmpeg: {err}"),
+            ExportError::FfmpegFailed(status) => write!(f, "ffmpeg failed: {status}"),
+            ExportError::TooLarge { width, height } => {
+                write!(f, "{width}x{height} is too large for a GIF")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExportError {}
+
+impl From<HeadlessError> for ExportError {
+    fn from(err: HeadlessError) -> Self {
+        ExportError::Headless(err)
+    }
+}
+
+impl From<std::io::Error> for ExportError {
+    fn from(err: std::io::Error) -> Self {
+        ExportError::Io(err)
+    }
+}
+
+impl From<png::EncodingError> for ExportError {
+    fn from(err: png::EncodingError) -> Self {
+        ExportError::Png(err)
+    }
+}
+
+impl From<gif::EncodingError> for ExportError {
+    fn from(err: gif::EncodingError) -> Self {
+        ExportError::Gif(err)
+    }
+}
+
+enum Sink {
+    Png(PathBuf),
+    Ffmpeg(Child, ChildStdin),
+    Gif(gif::Encoder<BufWriter<File>>),
+    Apng(png::Writer<BufWriter<File>>),
+}
+
+/// Writes frames from [`Headless`] to disk, one after another.
+pub struct Exporter {
+    width: u32,
+    height: u32,
+    fps: f32,
+    written: u32,
+    sink: Sink,
+}
+
+impl Exporter {
+    /// Starts an export of `frames` frames of `width` x `height` at `fps`.
+    /// Only APNG needs the frame count up front; the other formats take as
+    /// many frames as they're given.
+    pub fn create(
+        path: &Path,
+        format: ExportFormat,
+        width: u32,
+        height: u32,
+        fps: f32,
+        frames: u32,
+    ) -> Result<Exporter, ExportError> {
+        let sink = match format {
+            ExportFormat::PngSequence => {
+                std::fs::create_dir_all(path)?;
+                Sink::Png(path.to_path_buf())
+            }
+            ExportFormat::Video => {
+                let mut child = Command::new("ffmpeg")
+                    .args(["-y", "-loglevel", "error", "-f", "rawvideo"])
+                    .args(["-pix_fmt", "rgba", "-s", &format!("{width}x{height}")])
+                    .args(["-framerate", &fps.to_string(), "-i", "-"])
+                    // Most players can't decode anything else.
+                    .args(["-pix_fmt", "yuv420p"])
+                    .arg(path)
+                    .stdin(Stdio::piped())
+                    .spawn()
+                    .map_err(ExportError::FfmpegMissing)?;
+                let stdin = child.stdin.take().unwrap();
+                Sink::Ffmpeg(child, stdin)
+            }
+            ExportFormat::Gif => {
+                let (Ok(w), Ok(h)) = (u16::try_from(width), u16::try_from(height)) else {
+                    return Err(ExportError::TooLarge { width, height });
+                };
+                let mut encoder =
+                    gif::Encoder::new(BufWriter::new(File::create(path)?), w, h, &[])?;
+                encoder.set_repeat(gif::Repeat::Infinite)?;
+                Sink::Gif(encoder)
+            }
+            ExportFormat::Apng => {
+                let mut encoder =
+                    png::Encoder::new(BufWriter::new(File::create(path)?), width, height);
+                encoder.set_color(png::ColorType::Rgba);
+                encoder.set_depth(png::BitDepth::Eight);
+                encoder.set_animated(frames.max(1), 0)?;
+                // Delays are fractions of a second over a u16, so keep two
+                // decimals of the frame rate.
+                encoder.set_frame_delay(100, (fps * 100.0).round().min(u16::MAX as f32) as u16)?;
+                Sink::Apng(encoder.write_header()?)
+            }
+        };
+        Ok(Exporter {
+            width,
+            height,
+            fps,
+            written: 0,
+            sink,
+        })
+    }
+
+    /// Appends `frame`, tightly packed RGBA8 rows from the top as
+    /// [`Headless::render`] returns them. Frames are written opaque.
+    pub fn write(&mut self, frame: &[u8]) -> Result<(), ExportError> {
+        let mut frame = frame.to_vec();
+        for px in frame.chunks_mut(4) {
+            px[3] = 255;
+        }
+        match &mut self.sink {
+            Sink::Png(dir) => {
+                let path = dir.join(format!("frame_{:05}.png", self.written));
+                let mut encoder =
+                    png::Encoder::new(BufWriter::new(File::create(path)?), self.width, self.height);
+                encoder.set_color(png::ColorType::Rgba);
+                encoder.set_depth(png::BitDepth::Eight);
+                let mut writer = encoder.write_header()?;
+                writer.write_image_data(&frame)?;
+                writer.finish()?;
+            }
+            Sink::Ffmpeg(_, stdin) => stdin.write_all(&frame)?,
+            Sink::Gif(encoder) => {
+                let mut gif_frame = gif::Frame::from_rgba_speed(
+                    self.width as u16,
+                    self.height as u16,
+                    &mut frame,
+                    10,
+                );
+                // Delays are in whole centiseconds; round each frame's end
+                // time so rounding errors don't pile up over the reel.
+                let centis = |frame: u32| (frame as f64 * 100.0 / self.fps as f64).round() as u32;
+                gif_frame.delay = (centis(self.written + 1) - centis(self.written)) as u16;
+                encoder.write_frame(&gif_frame)?;
+            }
+            Sink::Apng(writer) => writer.write_image_data(&frame)?,
+        }
+        self.written += 1;
+        Ok(())
+    }
+
+    /// Flushes the export and waits for `ffmpeg` to finish, if it's used.
+    pub fn finish(self) -> Result<(), ExportError> {
+        match self.sink {
+            Sink::Png(_) => {}
+            Sink::Ffmpeg(mut child, stdin) => {
+                // Closing stdin tells ffmpeg there are no more frames.
+                drop(stdin);
+                let status = child.wait()?;
+                if !status.success() {
+                    return Err(ExportError::FfmpegFailed(status));
+                }
+            }
+            Sink::Gif(encoder) => {
+                encoder.into_inner()?.flush()?;
+            }
+            Sink::Apng(writer) => writer.finish()?,
+        }
+        Ok(())
+    }
+}
+
+/// Plays the reel `make` builds offscreen, as `options` ask, and exports it
+/// if they name a file. `make` gets the device to build on and the output
+/// size. Returns how many frames were rendered.
+pub fn run_headless<R: Reel>(
+    options: &Options,
+    make: impl FnOnce(&Headless, u32, u32) -> R,
+) -> Result<u32, ExportError> {
+    let (width, height) = options.size.unwrap_or(Options::HEADLESS_SIZE);
+    let headless = Headless::new(width, height)?;
+    let mut reel = make(&headless, width, height);
+    let frames = options.frames(reel.duration());
+    let Some(path) = &options.export else {
+        headless.run(&mut reel, options.fps, frames, |_, _| {
+            Ok::<_, ExportError>(())
+        })?;
+        return Ok(frames);
+    };
+    let format = options
+        .format
+        .unwrap_or_else(|| ExportFormat::from_path(path));
+    let mut exporter = Exporter::create(path, format, width, height, options.fps, frames)?;
+    headless.run(&mut reel, options.fps, frames, |_, frame| {
+        exporter.write(frame)
+    })?;
+    exporter.finish()?;
+    Ok(frames)
+}
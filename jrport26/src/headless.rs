@@ -0,0 +1,226 @@
+use commons::clock::Clock;
+
+use crate::reel::Reel;
+
+#[derive(Debug)]
+pub enum HeadlessError {
+    /// No adapter matched; set `WGPU_ADAPTER_NAME` (e.g. to `llvmpipe`) or
+    /// `WGPU_BACKEND` to pick one.
+    Adapter(wgpu::RequestAdapterError),
+    Device(wgpu::RequestDeviceError),
+}
+
+impl std::fmt::Display for HeadlessError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HeadlessError::Adapter(err) => write!(f, "no usable GPU adapter: {err}"),
+            HeadlessError::Device(err) => write!(f, "cannot open GPU device: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for HeadlessError {}
+
+/// Renders reels into an offscreen texture instead of a window, so they can
+/// run on machines without a display, including software adapters such as
+/// llvmpipe.
+pub struct Headless {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    width: u32,
+    height: u32,
+    format: wgpu::TextureFormat,
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+    readback: wgpu::Buffer,
+    /// Bytes per row in `readback`, padded to wgpu's copy alignment.
+    padded_row: u32,
+}
+
+impl Headless {
+    /// Format of the frames reels are drawn into.
+    pub const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+    /// Opens the adapter picked by the `WGPU_*` environment variables, or
+    /// the default one, and renders at `width` x `height`.
+    pub fn new(width: u32, height: u32) -> Result<Self, HeadlessError> {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::from_env_or_default());
+        let adapter = pollster::block_on(wgpu::util::initialize_adapter_from_env_or_default(
+            &instance, None,
+        ))
+        .map_err(HeadlessError::Adapter)?;
+        let (device, queue) = pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor {
+            label: Some("Headless"),
+            required_limits: wgpu::Limits::downlevel_defaults().using_resolution(adapter.limits()),
+            ..Default::default()
+        }))
+        .map_err(HeadlessError::Device)?;
+        Ok(Self::with_device(device, queue, width, height))
+    }
+
+    /// Renders at `width` x `height` on an existing device.
+    pub fn with_device(device: wgpu::Device, queue: wgpu::Queue, width: u32, height: u32) -> Self {
+        let (texture, view, readback, padded_row) =
+            frame_target(&device, width, height, Self::FORMAT);
+        Self {
+            device,
+            queue,
+            width: width.max(1),
+            height: height.max(1),
+            format: Self::FORMAT,
+            texture,
+            view,
+            readback,
+            padded_row,
+        }
+    }
+
+    /// Renders into `format`, which must have four bytes per pixel, instead
+    /// of `FORMAT`. Linear formats read back the values shaders wrote,
+    /// without sRGB encoding.
+    pub fn with_format(mut self, format: wgpu::TextureFormat) -> Self {
+        self.format = format;
+        self.resize(self.width, self.height);
+        self
+    }
+
+    /// Renders at `width` x `height` from now on. Reels drawn here need the
+    /// same `Reel::resize`.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        (self.texture, self.view, self.readback, self.padded_row) =
+            frame_target(&self.device, width, height, self.format);
+        (self.width, self.height) = (width.max(1), height.max(1));
+    }
+
+    pub fn device(&self) -> &wgpu::Device {
+        &self.device
+    }
+
+    pub fn queue(&self) -> &wgpu::Queue {
+        &self.queue
+    }
+
+    pub fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    pub fn format(&self) -> wgpu::TextureFormat {
+        self.format
+    }
+
+    /// Draws `reel`'s current frame and returns it as RGBA8 pixels (sRGB),
+    /// row by row from the top.
+    pub fn render(&self, reel: &mut dyn Reel) -> Vec<u8> {
+        self.render_with(|encoder, view| reel.render(encoder, &self.queue, view))
+    }
+
+    /// Clears the frame to black, lets `draw` record into it and returns it
+    /// as pixels, row by row from the top.
+    pub fn render_with(
+        &self,
+        draw: impl FnOnce(&mut wgpu::CommandEncoder, &wgpu::TextureView),
+    ) -> Vec<u8> {
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Headless frame"),
+            });
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Clear"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &self.view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            ..Default::default()
+        });
+        draw(&mut encoder, &self.view);
+        encoder.copy_texture_to_buffer(
+            self.texture.as_image_copy(),
+            wgpu::TexelCopyBufferInfo {
+                buffer: &self.readback,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(self.padded_row),
+                    rows_per_image: None,
+                },
+            },
+            self.texture.size(),
+        );
+        self.queue.submit(Some(encoder.finish()));
+
+        self.readback.map_async(wgpu::MapMode::Read, .., |result| {
+            result.expect("failed to map headless readback buffer")
+        });
+        self.device
+            .poll(wgpu::PollType::wait_indefinitely())
+            .expect("device lost while reading a frame");
+        let frame = self
+            .readback
+            .get_mapped_range(..)
+            .chunks(self.padded_row as usize)
+            .flat_map(|row| &row[..self.width as usize * 4])
+            .copied()
+            .collect();
+        self.readback.unmap();
+        frame
+    }
+
+    /// Plays `frames` frames of `reel` at `fps` on a fixed-step clock, so
+    /// each frame is exactly `1 / fps` seconds after the last, and hands
+    /// every frame to `each` along with its index. Stops at the first error
+    /// from `each`.
+    pub fn run<E>(
+        &self,
+        reel: &mut dyn Reel,
+        fps: f32,
+        frames: u32,
+        mut each: impl FnMut(u32, &[u8]) -> Result<(), E>,
+    ) -> Result<(), E> {
+        let mut clock = Clock::fixed(1.0 / fps);
+        for index in 0..frames {
+            clock.tick();
+            reel.update(&self.device, &self.queue, &clock);
+            each(index, &self.render(reel))?;
+        }
+        Ok(())
+    }
+}
+
+/// The texture frames are drawn into, a view of it, and the buffer they're
+/// read back through with its padded row length.
+fn frame_target(
+    device: &wgpu::Device,
+    width: u32,
+    height: u32,
+    format: wgpu::TextureFormat,
+) -> (wgpu::Texture, wgpu::TextureView, wgpu::Buffer, u32) {
+    let (width, height) = (width.max(1), height.max(1));
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Headless frame"),
+        size: wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&Default::default());
+    let padded_row = (width * 4).next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+    let readback = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Headless readback"),
+        size: padded_row as u64 * height as u64,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    (texture, view, readback, padded_row)
+}
@@ -0,0 +1,12 @@
+pub mod app;
+pub mod background;
+pub mod display;
+pub mod export;
+pub mod headless;
+pub mod options;
+pub mod particles;
+pub mod reel;
+pub mod reelone;
+pub mod reeltwo;
+pub mod script;
+pub mod trails;
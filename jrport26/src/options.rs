@@ -0,0 +1,241 @@
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use crate::display::{MonitorChoice, WindowMode};
+use crate::export::ExportFormat;
+
+/// Command-line options shared by the reel binaries. The window settings
+/// can also come from a `--config` file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Options {
+    /// Reel script to play, for reels that take one.
+    pub script: Option<PathBuf>,
+    /// Render offscreen without opening a window.
+    pub headless: bool,
+    /// Output size in pixels: the window's size, the resolution exclusive
+    /// fullscreen switches to, or the size of headless frames.
+    pub size: Option<(u32, u32)>,
+    /// Monitor to show the reel on; the primary one if not given or not
+    /// connected.
+    pub monitor: Option<MonitorChoice>,
+    /// Windowed or fullscreen; borderless fullscreen if not given.
+    pub mode: Option<WindowMode>,
+    /// Print the available monitors instead of playing.
+    pub list_monitors: bool,
+    /// File the window settings were read from.
+    pub config: Option<PathBuf>,
+    /// Frames per second when rendering headless.
+    pub fps: f32,
+    /// Seconds to render headless; defaults to one pass through the reel.
+    pub seconds: Option<f32>,
+    /// File or directory to export the headless frames to.
+    pub export: Option<PathBuf>,
+    /// Export format, if not the one `export`'s extension implies.
+    pub format: Option<ExportFormat>,
+    pub help: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            script: None,
+            headless: false,
+            size: None,
+            monitor: None,
+            mode: None,
+            list_monitors: false,
+            config: None,
+            fps: 60.0,
+            seconds: None,
+            export: None,
+            format: None,
+            help: false,
+        }
+    }
+}
+
+pub const USAGE: &str = "\
+usage: <reel> [options] [script.toml]
+
+options:
+  --headless          render offscreen, without a window
+  --size WxH          window or output size in pixels (windowed default
+                      1280x720, headless default 1920x1080)
+  --monitor NAME|N    monitor to play on, by name or index (default: primary)
+  --list-monitors     print the available monitors and exit
+  --mode MODE         windowed, borderless (default) or exclusive fullscreen
+  --config FILE       read monitor, mode and size from a TOML file; options
+                      on the command line take precedence
+  --fps N             headless frames per second (default 60)
+  --seconds S         headless length (default: one pass through the reel)
+  --export PATH       render headless into PATH: a directory of PNGs, or a
+                      .gif, .apng or video file (.mp4, .webm, ... via ffmpeg)
+  --format FORMAT     export as png, apng, gif or video whatever PATH's name
+  -h, --help          show this message
+";
+
+/// The settings a `--config` file can hold.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Config {
+    monitor: Option<MonitorChoice>,
+    mode: Option<WindowMode>,
+    size: Option<[u32; 2]>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum OptionsError {
+    UnknownOption(String),
+    MissingValue(&'static str),
+    BadValue {
+        option: &'static str,
+        value: String,
+    },
+    /// More than one script path was given.
+    ExtraArgument(String),
+    /// The `--config` file could not be read or parsed.
+    Config {
+        path: PathBuf,
+        message: String,
+    },
+}
+
+impl std::fmt::Display for OptionsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OptionsError::UnknownOption(option) => write!(f, "unknown option {option:?}"),
+            OptionsError::MissingValue(option) => write!(f, "{option} needs a value"),
+            OptionsError::BadValue { option, value } => {
+                write!(f, "bad value for {option}: {value:?}")
+            }
+            OptionsError::ExtraArgument(arg) => write!(f, "unexpected argument {arg:?}"),
+            OptionsError::Config { path, message } => {
+                write!(f, "bad config {}: {message}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for OptionsError {}
+
+impl Options {
+    /// Output size for headless runs without `--size`.
+    pub const HEADLESS_SIZE: (u32, u32) = (1920, 1080);
+
+    /// Parses arguments, not including the program name.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Options, OptionsError> {
+        let mut options = Options::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            // Accept both `--size 640x360` and `--size=640x360`.
+            let (name, inline) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => (name, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            let mut value = |option: &'static str| {
+                inline
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or(OptionsError::MissingValue(option))
+            };
+            match name {
+                "--headless" => options.headless = true,
+                "--size" => options.size = Some(parse_size(&value("--size")?)?),
+                "--fps" => options.fps = parse_positive("--fps", &value("--fps")?)?,
+                "--seconds" => {
+                    options.seconds = Some(parse_positive("--seconds", &value("--seconds")?)?)
+                }
+                "--monitor" => options.monitor = Some(value("--monitor")?.parse().unwrap()),
+                "--list-monitors" => options.list_monitors = true,
+                "--mode" => {
+                    let mode = value("--mode")?;
+                    options.mode = Some(mode.parse().map_err(|_| OptionsError::BadValue {
+                        option: "--mode",
+                        value: mode,
+                    })?)
+                }
+                "--config" => options.config = Some(PathBuf::from(value("--config")?)),
+                "--export" => {
+                    options.export = Some(PathBuf::from(value("--export")?));
+                    options.headless = true;
+                }
+                "--format" => {
+                    let format = value("--format")?;
+                    options.format = Some(format.parse().map_err(|_| OptionsError::BadValue {
+                        option: "--format",
+                        value: format,
+                    })?)
+                }
+                "-h" | "--help" => options.help = true,
+                _ if name.starts_with('-') && name.len() > 1 => {
+                    return Err(OptionsError::UnknownOption(arg));
+                }
+                _ if options.script.is_none() => options.script = Some(PathBuf::from(arg)),
+                _ => return Err(OptionsError::ExtraArgument(arg)),
+            }
+        }
+        Ok(options)
+    }
+
+    /// Parses arguments like `parse`, then fills in whatever they leave
+    /// unset from the `--config` file, if one is given.
+    pub fn load(args: impl IntoIterator<Item = String>) -> Result<Options, OptionsError> {
+        let mut options = Options::parse(args)?;
+        if let Some(path) = options.config.clone() {
+            let config_error = |message: String| OptionsError::Config {
+                path: path.clone(),
+                message,
+            };
+            let text = std::fs::read_to_string(&path).map_err(|e| config_error(e.to_string()))?;
+            options
+                .apply_config(&text)
+                .map_err(|e| config_error(e.to_string()))?;
+        }
+        Ok(options)
+    }
+
+    /// Fills in settings not given on the command line from `text`, a TOML
+    /// config such as
+    ///
+    /// ```toml
+    /// monitor = "HDMI-1"  # or an index, like 1
+    /// mode = "exclusive"
+    /// size = [1920, 1080]
+    /// ```
+    pub fn apply_config(&mut self, text: &str) -> Result<(), toml::de::Error> {
+        let config: Config = toml::from_str(text)?;
+        self.monitor = self.monitor.take().or(config.monitor);
+        self.mode = self.mode.or(config.mode);
+        self.size = self.size.or(config.size.map(|[w, h]| (w, h)));
+        Ok(())
+    }
+
+    /// How many frames a headless run renders, given the reel's length.
+    pub fn frames(&self, reel_duration: Option<f32>) -> u32 {
+        let seconds = self.seconds.or(reel_duration).unwrap_or(10.0);
+        (seconds * self.fps).round().max(1.0) as u32
+    }
+}
+
+fn parse_size(value: &str) -> Result<(u32, u32), OptionsError> {
+    let bad = || OptionsError::BadValue {
+        option: "--size",
+        value: value.to_string(),
+    };
+    let (w, h) = value.split_once(['x', 'X']).ok_or_else(bad)?;
+    match (w.trim().parse(), h.trim().parse()) {
+        (Ok(w), Ok(h)) if w > 0 && h > 0 => Ok((w, h)),
+        _ => Err(bad()),
+    }
+}
+
+fn parse_positive(option: &'static str, value: &str) -> Result<f32, OptionsError> {
+    match value.parse::<f32>() {
+        Ok(v) if v > 0.0 && v.is_finite() => Ok(v),
+        _ => Err(OptionsError::BadValue {
+            option,
+            value: value.to_string(),
+        }),
+    }
+}
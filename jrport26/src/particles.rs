@@ -0,0 +1,471 @@
+use commons::physics::{Particle, PhysicsParams};
+use wgpu::util::DeviceExt;
+
+/// Attractors beyond this many are ignored by the GPU simulation.
+pub const MAX_ATTRACTORS: usize = 8;
+
+const WORKGROUP_SIZE: u32 = 256;
+
+/// Samples of the morph easing curve uploaded by `set_morph`; the shader
+/// interpolates linearly between them.
+const MORPH_CURVE_SAMPLES: usize = 1024;
+
+#[repr(C)]
+#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+struct SimUniforms {
+    clock: [f32; 4],  // [first step time, step, morph start, morph duration]
+    spring: [f32; 4], // [stiffness, damping, drag, 0]
+    noise: [f32; 4],  // [noise strength, curl strength, noise scale, noise speed]
+    forces: [f32; 4], // [gravity x, gravity y, wind x, wind y]
+    offset: [f32; 4], // [target offset x, target offset y, 0, 0]
+    counts: [u32; 4], // [steps, particles, attractors, 0]
+    // Each [x, y, strength, radius]
+    attractors: [[f32; 4]; MAX_ATTRACTORS],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+struct ViewUniforms {
+    params: [f32; 4], // [res_x, res_y, radius, softness]
+    tint: [f32; 4],
+    motion: [f32; 4], // [streak seconds, 0, 0, 0]
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpriteBlend {
+    /// Overlapping particles add up and glow.
+    Additive,
+    /// Particles cover what is behind them by their alpha.
+    Alpha,
+}
+
+/// How particles are splatted: soft, anti-aliased discs.
+#[derive(Clone, Copy, Debug)]
+pub struct SpriteStyle {
+    /// Disc radius in pixels. Below one pixel each particle is spread over
+    /// its nearest pixels with bilinear weights instead.
+    pub radius: f32,
+    /// Fraction of the radius over which the disc fades out; 0 is a hard
+    /// (but still anti-aliased) edge, 1 fades all the way from the center.
+    pub softness: f32,
+    pub blend: SpriteBlend,
+    /// Multiplied into every particle's color, e.g. to follow the current
+    /// quote's color.
+    pub tint: [f32; 4],
+    /// Directional blur: each particle is smeared back along its velocity
+    /// over this many seconds of motion.
+    pub streak: f32,
+}
+
+impl Default for SpriteStyle {
+    fn default() -> Self {
+        Self {
+            radius: 1.5,
+            softness: 0.5,
+            blend: SpriteBlend::Additive,
+            tint: [1.0; 4],
+            streak: 0.0,
+        }
+    }
+}
+
+/// Particle state kept in GPU buffers, stepped by a compute shader and drawn
+/// as instanced quads. Needs nothing beyond wgpu's default limits, so it also
+/// runs on software adapters (e.g. `WGPU_ADAPTER_NAME=llvmpipe`).
+///
+/// Each particle springs toward its target, which morphs from a start point
+/// to an end point over the window set by `set_morph`.
+pub struct GpuParticles {
+    count: u32,
+    morph_start: f32,
+    morph_duration: f32,
+    /// Added to every target, in pixels.
+    pub target_offset: (f32, f32),
+    pub style: SpriteStyle,
+
+    particles: wgpu::Buffer,
+    targets: wgpu::Buffer,
+    morph_curve: wgpu::Buffer,
+    colors: wgpu::Buffer,
+    sim_uniforms: wgpu::Buffer,
+    sim_bind_group: wgpu::BindGroup,
+    sim_pipeline: wgpu::ComputePipeline,
+    view_uniforms: wgpu::Buffer,
+    draw_bind_group: wgpu::BindGroup,
+    additive_pipeline: wgpu::RenderPipeline,
+    alpha_pipeline: wgpu::RenderPipeline,
+}
+
+impl GpuParticles {
+    /// Uploads `particles` and builds pipelines that draw into targets of
+    /// `format`.
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat, particles: &[Particle]) -> Self {
+        let count = particles.len() as u32;
+        let packed: Vec<[f32; 4]> = particles.iter().map(|p| [p.x, p.y, p.vx, p.vy]).collect();
+        let particle_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Particles"),
+            contents: bytemuck::cast_slice(&packed),
+            usage: wgpu::BufferUsages::STORAGE
+                | wgpu::BufferUsages::VERTEX
+                | wgpu::BufferUsages::COPY_SRC
+                | wgpu::BufferUsages::COPY_DST,
+        });
+        let targets = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Particle targets"),
+            contents: bytemuck::cast_slice(&vec![[0.0f32; 4]; particles.len()]),
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+        });
+        let linear: Vec<f32> = (0..MORPH_CURVE_SAMPLES)
+            .map(|i| i as f32 / (MORPH_CURVE_SAMPLES - 1) as f32)
+            .collect();
+        let morph_curve = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Particle morph curve"),
+            contents: bytemuck::cast_slice(&linear),
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+        });
+        let colors = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Particle colors"),
+            contents: bytemuck::cast_slice(&vec![[1.0f32; 4]; particles.len()]),
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        });
+        let sim_uniforms = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Particle simulation uniforms"),
+            size: std::mem::size_of::<SimUniforms>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let view_uniforms = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Particle view uniforms"),
+            size: std::mem::size_of::<ViewUniforms>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let storage = |binding: u32, read_only: bool| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        let uniform = |visibility: wgpu::ShaderStages| wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+
+        let sim_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Particle simulation"),
+            entries: &[
+                uniform(wgpu::ShaderStages::COMPUTE),
+                storage(1, false),
+                storage(2, true),
+                storage(3, true),
+            ],
+        });
+        let sim_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Particle simulation"),
+            layout: &sim_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: sim_uniforms.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: particle_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: targets.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: morph_curve.as_entire_binding(),
+                },
+            ],
+        });
+        let sim_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("particles.wgsl"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("particles.wgsl").into()),
+        });
+        let sim_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Particle simulation"),
+            layout: Some(
+                &device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                    bind_group_layouts: &[&sim_layout],
+                    ..Default::default()
+                }),
+            ),
+            module: &sim_shader,
+            entry_point: Some("simulate"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+        let draw_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Particle sprites"),
+            entries: &[uniform(wgpu::ShaderStages::VERTEX_FRAGMENT)],
+        });
+        let draw_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Particle sprites"),
+            layout: &draw_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: view_uniforms.as_entire_binding(),
+            }],
+        });
+        let draw_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("sprites.wgsl"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("sprites.wgsl").into()),
+        });
+        let draw_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            bind_group_layouts: &[&draw_layout],
+            ..Default::default()
+        });
+        let particle_attributes = wgpu::vertex_attr_array![0 => Float32x4];
+        let color_attributes = wgpu::vertex_attr_array![1 => Float32x4];
+        let instance_buffers =
+            [&particle_attributes, &color_attributes].map(|attributes| wgpu::VertexBufferLayout {
+                array_stride: std::mem::size_of::<[f32; 4]>() as u64,
+                step_mode: wgpu::VertexStepMode::Instance,
+                attributes,
+            });
+        // The fragment shader outputs premultiplied color.
+        let draw_pipeline = |label: &str, dst_factor: wgpu::BlendFactor| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(&draw_pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &draw_shader,
+                    entry_point: Some("vs_main"),
+                    buffers: &instance_buffers,
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &draw_shader,
+                    entry_point: Some("fs_main"),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format,
+                        blend: Some(wgpu::BlendState {
+                            color: wgpu::BlendComponent {
+                                src_factor: wgpu::BlendFactor::One,
+                                dst_factor,
+                                operation: wgpu::BlendOperation::Add,
+                            },
+                            alpha: wgpu::BlendComponent::OVER,
+                        }),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: Default::default(),
+                }),
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview_mask: None,
+                cache: None,
+            })
+        };
+        let additive_pipeline =
+            draw_pipeline("Particle sprites (additive)", wgpu::BlendFactor::One);
+        let alpha_pipeline = draw_pipeline(
+            "Particle sprites (alpha)",
+            wgpu::BlendFactor::OneMinusSrcAlpha,
+        );
+
+        Self {
+            count,
+            morph_start: 0.0,
+            morph_duration: 1.0,
+            target_offset: (0.0, 0.0),
+            style: SpriteStyle::default(),
+            particles: particle_buffer,
+            targets,
+            morph_curve,
+            colors,
+            sim_uniforms,
+            sim_bind_group,
+            sim_pipeline,
+            view_uniforms,
+            draw_bind_group,
+            additive_pipeline,
+            alpha_pipeline,
+        }
+    }
+
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Sets each particle's morph start and end; particle `i` uses entry
+    /// `i % len` of each list.
+    pub fn set_targets(&self, queue: &wgpu::Queue, from: &[(f32, f32)], to: &[(f32, f32)]) {
+        if from.is_empty() || to.is_empty() {
+            return;
+        }
+        let packed: Vec<[f32; 4]> = (0..self.count as usize)
+            .map(|i| {
+                let (a, b) = (from[i % from.len()], to[i % to.len()]);
+                [a.0, a.1, b.0, b.1]
+            })
+            .collect();
+        queue.write_buffer(&self.targets, 0, bytemuck::cast_slice(&packed));
+    }
+
+    /// Morphs targets from their start to their end over `duration` seconds
+    /// from `start`, with progress following `easing` (which maps 0..=1 to
+    /// 0..=1). Targets hold their start before the window and their end
+    /// after it.
+    pub fn set_morph(
+        &mut self,
+        queue: &wgpu::Queue,
+        start: f32,
+        duration: f32,
+        easing: impl Fn(f32) -> f32,
+    ) {
+        let curve: Vec<f32> = (0..MORPH_CURVE_SAMPLES)
+            .map(|i| easing(i as f32 / (MORPH_CURVE_SAMPLES - 1) as f32))
+            .collect();
+        queue.write_buffer(&self.morph_curve, 0, bytemuck::cast_slice(&curve));
+        self.morph_start = start;
+        self.morph_duration = duration.max(1e-6);
+    }
+
+    /// Sets each particle's color and alpha (straight, not premultiplied);
+    /// particle `i` uses entry `i % len`.
+    pub fn set_colors(&self, queue: &wgpu::Queue, colors: &[[f32; 4]]) {
+        if colors.is_empty() {
+            return;
+        }
+        let packed: Vec<[f32; 4]> = (0..self.count as usize)
+            .map(|i| colors[i % colors.len()])
+            .collect();
+        queue.write_buffer(&self.colors, 0, bytemuck::cast_slice(&packed));
+    }
+
+    /// Runs `steps` physics steps of `step` seconds, the first at `time`, and
+    /// submits the work.
+    pub fn simulate(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        params: &PhysicsParams,
+        time: f32,
+        steps: u32,
+        step: f32,
+    ) {
+        if steps == 0 || self.count == 0 {
+            return;
+        }
+        let mut attractors = [[0.0; 4]; MAX_ATTRACTORS];
+        for (slot, a) in attractors.iter_mut().zip(&params.attractors) {
+            *slot = [a.x, a.y, a.strength, a.radius];
+        }
+        let uniforms = SimUniforms {
+            clock: [time, step, self.morph_start, self.morph_duration],
+            spring: [params.stiffness, params.damping, params.drag, 0.0],
+            noise: [
+                params.noise_strength,
+                params.curl_strength,
+                params.noise_scale,
+                params.noise_speed,
+            ],
+            forces: [
+                params.gravity.0,
+                params.gravity.1,
+                params.wind.0,
+                params.wind.1,
+            ],
+            offset: [self.target_offset.0, self.target_offset.1, 0.0, 0.0],
+            counts: [
+                steps,
+                self.count,
+                params.attractors.len().min(MAX_ATTRACTORS) as u32,
+                0,
+            ],
+            attractors,
+        };
+        queue.write_buffer(&self.sim_uniforms, 0, bytemuck::bytes_of(&uniforms));
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Particle simulation"),
+        });
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Particle simulation"),
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(&self.sim_pipeline);
+            pass.set_bind_group(0, &self.sim_bind_group, &[]);
+            pass.dispatch_workgroups(self.count.div_ceil(WORKGROUP_SIZE), 1, 1);
+        }
+        queue.submit(Some(encoder.finish()));
+    }
+
+    /// Updates the output size the sprites are projected into, along with
+    /// `style`.
+    pub fn set_view(&self, queue: &wgpu::Queue, width: f32, height: f32) {
+        let uniforms = ViewUniforms {
+            params: [
+                width,
+                height,
+                self.style.radius.max(0.0),
+                self.style.softness.clamp(1e-3, 1.0),
+            ],
+            tint: self.style.tint,
+            motion: [self.style.streak.max(0.0), 0.0, 0.0, 0.0],
+        };
+        queue.write_buffer(&self.view_uniforms, 0, bytemuck::bytes_of(&uniforms));
+    }
+
+    pub fn draw(&self, rpass: &mut wgpu::RenderPass<'_>) {
+        rpass.set_pipeline(match self.style.blend {
+            SpriteBlend::Additive => &self.additive_pipeline,
+            SpriteBlend::Alpha => &self.alpha_pipeline,
+        });
+        rpass.set_bind_group(0, &self.draw_bind_group, &[]);
+        rpass.set_vertex_buffer(0, self.particles.slice(..));
+        rpass.set_vertex_buffer(1, self.colors.slice(..));
+        rpass.draw(0..6, 0..self.count);
+    }
+
+    /// Copies the particle state back to the CPU, blocking until the GPU is
+    /// done. Meant for tests and debugging, not per-frame use.
+    pub fn read_particles(&self, device: &wgpu::Device, queue: &wgpu::Queue) -> Vec<Particle> {
+        let size = self.count as u64 * std::mem::size_of::<[f32; 4]>() as u64;
+        if size == 0 {
+            return Vec::new();
+        }
+        let staging = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Particle readback"),
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Particle readback"),
+        });
+        encoder.copy_buffer_to_buffer(&self.particles, 0, &staging, 0, size);
+        queue.submit(Some(encoder.finish()));
+
+        staging.map_async(wgpu::MapMode::Read, .., |result| {
+            result.expect("failed to map particle readback buffer")
+        });
+        device
+            .poll(wgpu::PollType::wait_indefinitely())
+            .expect("device lost while reading particles");
+        let data = staging.get_mapped_range(..);
+        bytemuck::cast_slice::<u8, [f32; 4]>(&data)
+            .iter()
+            .map(|&[x, y, vx, vy]| Particle { x, y, vx, vy })
+            .collect()
+    }
+}
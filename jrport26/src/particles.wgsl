@@ -0,0 +1,95 @@
+// GPU port of `commons::physics::step_particles`: each invocation runs all of
+// this frame's fixed steps for one particle.
+
+struct SimParams {
+    clock: vec4<f32>,  // x: time of the first step, y: step, z: morph start, w: morph duration
+    spring: vec4<f32>, // x: stiffness, y: damping, z: drag
+    noise: vec4<f32>,  // x: noise strength, y: curl strength, z: scale, w: speed
+    forces: vec4<f32>, // xy: gravity, zw: wind
+    offset: vec4<f32>, // xy: added to every target
+    counts: vec4<u32>, // x: steps, y: particles, z: attractors
+    attractors: array<vec4<f32>, 8>, // xy: position, z: strength, w: radius
+};
+
+@group(0) @binding(0) var<uniform> sim: SimParams;
+@group(0) @binding(1) var<storage, read_write> particles: array<vec4<f32>>; // xy: position, zw: velocity
+@group(0) @binding(2) var<storage, read> targets: array<vec4<f32>>; // xy: morph start, zw: morph end
+@group(0) @binding(3) var<storage, read> morph_curve: array<f32>; // eased progress, sampled evenly over [0, 1]
+
+fn hash(x: i32, y: i32, seed: u32) -> u32 {
+    var h = (bitcast<u32>(x) * 0x8DA6B343u) ^ (bitcast<u32>(y) * 0xD8163841u) ^ (seed * 0xCB1AB31Fu);
+    h ^= h >> 16u;
+    h *= 0x7FEB352Du;
+    h ^= h >> 15u;
+    h *= 0x846CA68Bu;
+    return h ^ (h >> 16u);
+}
+
+fn gradient_noise(p: vec2<f32>, seed: u32) -> f32 {
+    let cell = floor(p);
+    let f = p - cell;
+    let i = vec2<i32>(cell);
+    let u = f * f * f * (f * (f * 6.0 - 15.0) + 10.0);
+
+    var corners: array<f32, 4>;
+    for (var k = 0; k < 4; k++) {
+        let c = vec2<i32>(k & 1, k >> 1u);
+        let angle = f32(hash(i.x + c.x, i.y + c.y, seed)) * (6.2831855 / 4294967296.0);
+        corners[k] = dot(vec2<f32>(cos(angle), sin(angle)), f - vec2<f32>(c));
+    }
+    let bottom = mix(corners[0], corners[1], u.x);
+    let top = mix(corners[2], corners[3], u.x);
+    return mix(bottom, top, u.y) * 1.4142135;
+}
+
+fn curl_noise(p: vec2<f32>, seed: u32) -> vec2<f32> {
+    let e = 1e-2;
+    let dx = (gradient_noise(p + vec2(e, 0.0), seed) - gradient_noise(p - vec2(e, 0.0), seed)) / (2.0 * e);
+    let dy = (gradient_noise(p + vec2(0.0, e), seed) - gradient_noise(p - vec2(0.0, e), seed)) / (2.0 * e);
+    return vec2<f32>(dy, -dx);
+}
+
+fn morph_progress(time: f32) -> f32 {
+    let last = arrayLength(&morph_curve) - 1u;
+    let x = clamp((time - sim.clock.z) / sim.clock.w, 0.0, 1.0) * f32(last);
+    let i = min(u32(x), last - 1u);
+    return mix(morph_curve[i], morph_curve[i + 1u], x - f32(i));
+}
+
+@compute @workgroup_size(256)
+fn simulate(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    if i >= sim.counts.y {
+        return;
+    }
+
+    var p = particles[i];
+    let morph = targets[i];
+    let h = sim.clock.y;
+    for (var k = 0u; k < sim.counts.x; k++) {
+        let time = sim.clock.x + f32(k) * h;
+        let target_pos = mix(morph.xy, morph.zw, morph_progress(time)) + sim.offset.xy;
+
+        var a = sim.spring.x * (target_pos - p.xy) - sim.spring.y * p.zw;
+        a += sim.forces.xy + sim.spring.z * (sim.forces.zw - p.zw);
+
+        if sim.noise.x != 0.0 || sim.noise.y != 0.0 {
+            let n = p.xy * sim.noise.z + vec2<f32>(time * sim.noise.w, 0.0);
+            a += sim.noise.x * vec2<f32>(gradient_noise(n, 0u), gradient_noise(n, 1u));
+            a += sim.noise.y * curl_noise(n, 2u);
+        }
+
+        for (var j = 0u; j < sim.counts.z; j++) {
+            let attractor = sim.attractors[j];
+            let d = attractor.xy - p.xy;
+            let dist = length(d);
+            if dist > 1e-3 && dist < attractor.w {
+                a += d * (attractor.z * (1.0 - dist / attractor.w) / dist);
+            }
+        }
+
+        p = vec4<f32>(p.xy, p.zw + a * h);
+        p = vec4<f32>(p.xy + p.zw * h, p.zw);
+    }
+    particles[i] = p;
+}
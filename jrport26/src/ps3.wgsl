@@ -1,9 +1,7 @@
 struct Globals {
-    params: vec4<f32>, // x: time, y: transition, z: res_x, w: res_y
-    color_a_old: vec4<f32>,
-    color_a_new: vec4<f32>,
-    color_b_old: vec4<f32>,
-    color_b_new: vec4<f32>,
+    params: vec4<f32>, // x: time, z: res_x, w: res_y
+    color_a: vec4<f32>,
+    color_b: vec4<f32>,
 };
 
 @group(0) @binding(0) var<uniform> globals: Globals;
@@ -18,16 +16,15 @@ fn vs_main(@builtin(vertex_index) idx: u32) -> @builtin(position) vec4<f32> {
 @fragment
 fn fs_main(@builtin(position) clip_pos: vec4<f32>) -> @location(0) vec4<f32> {
     let time = globals.params.x;
-    let mix_progress = globals.params.y; // 0.0 to 1.0
     let res = globals.params.zw;
     
     let uv = clip_pos.xy / res;
     let p = (uv - 0.5) * vec2<f32>(res.x / res.y, 1.0);
 
     // --- STEP 1: Temporal Smoothing ---
-    // This smoothly interpolates the static colors over time
-    let current_a = mix(globals.color_a_old.rgb, globals.color_a_new.rgb, mix_progress);
-    let current_b = mix(globals.color_b_old.rgb, globals.color_b_new.rgb, mix_progress);
+    // The colors arrive already animated between scenes
+    let current_a = globals.color_a.rgb;
+    let current_b = globals.color_b.rgb;
 
     // --- STEP 2: Spatial Mesh Logic ---
     let p1 = vec2<f32>(sin(time * 0.4), cos(time * 0.3)) * 0.5;
@@ -0,0 +1,27 @@
+use commons::clock::Clock;
+
+/// An animation that can be stepped and drawn into any target, whether a
+/// window surface or an offscreen texture.
+pub trait Reel {
+    /// Catches the animation up with `clock`, which has just ticked.
+    fn update(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, clock: &Clock);
+
+    /// Draws the current frame on top of `target`, which has the format and
+    /// size the reel was created for. The target should be cleared to black
+    /// (or hold a frame cleared to black) beforehand.
+    fn render(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        queue: &wgpu::Queue,
+        target: &wgpu::TextureView,
+    );
+
+    /// Adapts the reel to a target of `width` x `height`, such as a window
+    /// that was resized or moved to a monitor with a different scale.
+    fn resize(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, width: u32, height: u32);
+
+    /// Seconds until the reel starts over, if it loops.
+    fn duration(&self) -> Option<f32> {
+        None
+    }
+}
@@ -0,0 +1,346 @@
+use commons::assignment::{AssignmentMethod, assign_points};
+use commons::clock::Clock;
+use commons::easing::Easing;
+use commons::layout::{Align, LayoutOptions, VerticalAlign, layout_text};
+use commons::physics::{FixedTimestep, Particle, PhysicsParams};
+use commons::sampling::{SampleMethod, SampleOptions, SplitMix64, sample_text};
+use commons::timeline::{Timeline, Track};
+use commons::{GlyphSet, HAXOR_FONT, load_bdf};
+
+use crate::background::Background;
+use crate::particles::{GpuParticles, SpriteBlend, SpriteStyle};
+use crate::reel::Reel;
+use crate::script::{Script, Transition};
+use crate::trails::{TrailSettings, Trails};
+
+const PARTICLE_COUNT: usize = 3000;
+
+/// Seconds per physics step.
+const PHYSICS_STEP: f32 = 1.0 / 120.0;
+
+#[repr(C)]
+#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+struct Globals {
+    params: [f32; 4],  // [time, 0, res_x, res_y]
+    color_a: [f32; 4], // Blended around the first moving point
+    color_b: [f32; 4], // Blended around the second moving point
+}
+
+/// Reel one: a script's quotes spelled out in particles that morph from one
+/// to the next over a drifting color field.
+pub struct ReelOne {
+    width: u32,
+    height: u32,
+    /// Clock time at the last `update`.
+    time: f32,
+    /// Length of the last `update`, which sets how far trails fade.
+    frame_dt: f32,
+    timestep: FixedTimestep,
+    /// The clock's seek count at the last `update`.
+    seeks: u32,
+    physics: PhysicsParams,
+    script: Script,
+    glyphs: GlyphSet,
+    scenes: Timeline<usize>,
+    background_colors: [Track<[f32; 3]>; 2],
+    glow: Track<[f32; 3]>,
+
+    background: Background,
+    particles: GpuParticles,
+    trails: Trails,
+
+    /// The scene being shown, once one is loaded.
+    current_idx: Option<usize>,
+    /// When the current scene's morph started.
+    scene_start: f32,
+    pts_a: Vec<(f32, f32)>,
+    pts_b: Vec<(f32, f32)>,
+}
+
+impl ReelOne {
+    /// Short trails that glow a little, drift upwards and slowly change hue.
+    pub const TRAILS: TrailSettings = TrailSettings {
+        half_life: 0.05,
+        ghost: 0.15,
+        drift: (0.0, -12.0),
+        hue_shift: 0.3,
+    };
+
+    /// Sets up `script` to play at `width` x `height` into targets of
+    /// `format`.
+    pub fn new(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+        script: Script,
+    ) -> Self {
+        let glyphs = GlyphSet::from_font(&load_bdf(HAXOR_FONT).unwrap().subset(script.chars()));
+        let color = |idx: usize| script.scenes[idx].color;
+        // Colors fade in from black on the first scene.
+        let previous = |idx: usize| idx.checked_sub(1).map_or([0.0; 3], color);
+        let following = |idx: usize| color(script.next(idx));
+        let background_colors = [
+            scene_track(&script, previous, color),
+            scene_track(&script, color, following),
+        ];
+        let glow = scene_track(
+            &script,
+            |idx| script.scenes[idx].glow(),
+            |idx| script.scenes[script.next(idx)].glow(),
+        );
+
+        let background = Background::new(
+            device,
+            format,
+            "ps3.wgsl",
+            include_str!("ps3.wgsl"),
+            &Globals {
+                params: [0.0, 0.0, width as f32, height as f32],
+                color_a: [0.0, 0.0, 0.0, 1.0],
+                color_b: [0.0, 0.0, 0.0, 1.0],
+            },
+        );
+
+        let start = Particle::at(width as f32 / 2.0, height as f32 / 2.0);
+        let mut particles = GpuParticles::new(device, Trails::FORMAT, &vec![start; PARTICLE_COUNT]);
+        particles.style = SpriteStyle {
+            radius: 1.5,
+            softness: 0.6,
+            blend: SpriteBlend::Additive,
+            streak: 0.015,
+            ..Default::default()
+        };
+        // Vary brightness a little so the text reads as dust, not a stencil.
+        let mut rng = SplitMix64::new(PARTICLE_COUNT as u64);
+        let colors: Vec<[f32; 4]> = (0..PARTICLE_COUNT)
+            .map(|_| [1.0, 1.0, 1.0, 0.4 + 0.6 * rng.next_f32()])
+            .collect();
+        particles.set_colors(queue, &colors);
+
+        let mut trails = Trails::new(device, format, width, height);
+        trails.settings = Self::TRAILS;
+
+        let mut timestep = FixedTimestep::new(PHYSICS_STEP);
+        timestep.max_steps = 30;
+
+        Self {
+            width,
+            height,
+            time: 0.0,
+            frame_dt: 0.0,
+            timestep,
+            seeks: 0,
+            physics: PhysicsParams {
+                stiffness: 70.0,
+                damping: 10.0,
+                noise_strength: 30.0,
+                curl_strength: 400.0,
+                noise_scale: 0.006,
+                ..Default::default()
+            },
+            scenes: script.timeline(),
+            background_colors,
+            glow,
+            script,
+            glyphs,
+            background,
+            particles,
+            trails,
+            current_idx: None,
+            scene_start: 0.0,
+            pts_a: Vec::new(),
+            pts_b: Vec::new(),
+        }
+    }
+
+    /// Samples scene `idx` and the one after it, and hands them to the
+    /// particles as their morph targets, starting the morph at `start`.
+    fn load_scene(&mut self, queue: &wgpu::Queue, idx: usize, start: f32) {
+        // The previous morph ended on this scene, so keep its particle order
+        // instead of re-sampling.
+        let continues = self.current_idx.map(|current| self.script.next(current)) == Some(idx);
+        self.current_idx = Some(idx);
+        self.scene_start = start;
+
+        self.pts_a = if continues {
+            std::mem::take(&mut self.pts_b)
+        } else {
+            self.text_points(idx)
+        };
+
+        let next = self.text_points(self.script.next(idx));
+        self.pts_b = assign_points(&self.pts_a, &next, AssignmentMethod::default());
+
+        let scene = &self.script.scenes[idx];
+        self.particles.set_targets(queue, &self.pts_a, &self.pts_b);
+        self.particles.set_morph(queue, start, scene.duration, |t| {
+            scene.morph_progress(t * scene.duration)
+        });
+    }
+
+    fn text_points(&self, idx: usize) -> Vec<(f32, f32)> {
+        get_text_points(
+            &self.script.scenes[idx].text,
+            &self.glyphs,
+            self.width,
+            self.height,
+        )
+    }
+
+    /// Runs `steps` physics steps, the first of them step number `first`.
+    fn simulate(&self, device: &wgpu::Device, queue: &wgpu::Queue, first: u64, steps: u32) {
+        let time = first as f32 * PHYSICS_STEP;
+        self.particles
+            .simulate(device, queue, &self.physics, time, steps, PHYSICS_STEP);
+    }
+}
+
+impl Reel for ReelOne {
+    /// Runs fixed physics steps up to the clock's time, batched into one GPU
+    /// dispatch and split wherever the scene changes.
+    fn update(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, clock: &Clock) {
+        self.time = clock.time();
+        self.frame_dt = clock.dt();
+        if clock.seeks() != self.seeks {
+            // Start the scene the seek lands in afresh, rather than morphing
+            // from wherever the particles were. Seeking a step short makes
+            // this update run the step that loads it.
+            self.seeks = clock.seeks();
+            self.current_idx = None;
+            self.timestep.seek(self.time - PHYSICS_STEP);
+        }
+
+        let count = self.timestep.advance_to(self.time);
+        let last = self.timestep.steps();
+        let from = last + 1 - count as u64;
+        let (mut first, mut pending) = (from, 0);
+        for step in from..=last {
+            let time = step as f32 * PHYSICS_STEP;
+            let scene = self.scenes.at(time).unwrap();
+            let (idx, start) = (*scene.item, time - scene.local);
+            if Some(idx) != self.current_idx {
+                self.simulate(device, queue, first, pending);
+                self.load_scene(queue, idx, start);
+                (first, pending) = (step, 0);
+            }
+            pending += 1;
+        }
+        self.simulate(device, queue, first, pending);
+    }
+
+    /// Resizes the trails and lays the current scene out again to fit. The
+    /// particles spring over to their new targets, and the morph carries on
+    /// where it was.
+    fn resize(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, width: u32, height: u32) {
+        if (width, height) == (self.width, self.height) {
+            return;
+        }
+        (self.width, self.height) = (width, height);
+        self.trails.resize(device, width, height);
+        if let Some(idx) = self.current_idx.take() {
+            self.load_scene(queue, idx, self.scene_start);
+        }
+    }
+
+    fn render(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        queue: &wgpu::Queue,
+        target: &wgpu::TextureView,
+    ) {
+        let (width, height) = (self.width as f32, self.height as f32);
+        let [color_a, color_b] = self.background_colors.each_ref().map(|track| {
+            let [r, g, b] = track.sample(self.time);
+            [r, g, b, 1.0]
+        });
+        self.background.update(
+            queue,
+            &Globals {
+                params: [self.time, 0.0, width, height],
+                color_a,
+                color_b,
+            },
+        );
+
+        // Particles glow in the scene's hue, at full brightness.
+        let [r, g, b] = self.glow.sample(self.time);
+        self.particles.style.tint = [r, g, b, 1.0];
+        self.particles.set_view(queue, width, height);
+
+        // While paused the trails hold still instead of piling up the same
+        // frame.
+        if self.frame_dt > 0.0 {
+            let particles = &self.particles;
+            self.trails
+                .render(encoder, queue, self.frame_dt, |rpass| particles.draw(rpass));
+        }
+        let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            ..Default::default()
+        });
+        self.background.draw(&mut rpass);
+        self.trails.composite(&mut rpass);
+    }
+
+    fn duration(&self) -> Option<f32> {
+        Some(self.scenes.duration())
+    }
+}
+
+/// A color that goes from `from(idx)` to `to(idx)` over each scene, easing
+/// smoothly whatever the particles' easing. A cut holds `from(idx)` until
+/// the next scene.
+fn scene_track(
+    script: &Script,
+    from: impl Fn(usize) -> [f32; 3],
+    to: impl Fn(usize) -> [f32; 3],
+) -> Track<[f32; 3]> {
+    let mut track = Track::new(from(0));
+    for span in script.timeline().spans() {
+        let idx = span.item;
+        track = track.key(span.start, from(idx), Easing::Linear);
+        track = match script.scenes[idx].transition {
+            Transition::Morph => track.key(span.end(), to(idx), Easing::Smoothstep),
+            Transition::Cut => track.key(span.end(), from(idx), Easing::Linear),
+        };
+    }
+    track.looping(true)
+}
+
+fn get_text_points(text: &str, glyphs: &GlyphSet, width: u32, height: u32) -> Vec<(f32, f32)> {
+    let margin = 50.0;
+    let layout = layout_text(
+        text,
+        glyphs,
+        (width as f32, height as f32),
+        &LayoutOptions {
+            wrap_width: Some(width as f32 - 2.0 * margin),
+            align: Align::Center,
+            vertical_align: VerticalAlign::Middle,
+            scale: 3.0,
+            pixel_snap: true,
+            ..Default::default()
+        },
+    );
+
+    sample_text(
+        &layout,
+        glyphs,
+        PARTICLE_COUNT,
+        &SampleOptions {
+            method: SampleMethod::BlueNoise { candidates: 8 },
+            ..Default::default()
+        },
+    )
+}
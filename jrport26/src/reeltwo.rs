@@ -0,0 +1,82 @@
+use commons::clock::Clock;
+
+use crate::background::Background;
+use crate::reel::Reel;
+
+#[repr(C)]
+#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+struct Globals {
+    params: [f32; 4], // [time, res_x, res_y, unused]
+}
+
+/// Reel two: a single evolving shader.
+pub struct ReelTwo {
+    width: u32,
+    height: u32,
+    time: f32,
+    background: Background,
+}
+
+impl ReelTwo {
+    /// Sets up the reel at `width` x `height` for targets of `format`.
+    pub fn new(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        let background = Background::new(
+            device,
+            format,
+            "reel2.wgsl",
+            include_str!("reel2.wgsl"),
+            &Globals {
+                params: [0.0, width as f32, height as f32, 0.0],
+            },
+        );
+        Self {
+            width,
+            height,
+            time: 0.0,
+            background,
+        }
+    }
+}
+
+impl Reel for ReelTwo {
+    fn update(&mut self, _device: &wgpu::Device, _queue: &wgpu::Queue, clock: &Clock) {
+        self.time = clock.time();
+    }
+
+    fn resize(&mut self, _device: &wgpu::Device, _queue: &wgpu::Queue, width: u32, height: u32) {
+        (self.width, self.height) = (width, height);
+    }
+
+    fn render(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        queue: &wgpu::Queue,
+        target: &wgpu::TextureView,
+    ) {
+        self.background.update(
+            queue,
+            &Globals {
+                params: [self.time, self.width as f32, self.height as f32, 0.0],
+            },
+        );
+        let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            ..Default::default()
+        });
+        self.background.draw(&mut rpass);
+    }
+}
@@ -0,0 +1,163 @@
+use commons::easing::{Curve, Easing};
+use commons::timeline::Timeline;
+use serde::Deserialize;
+use std::path::Path;
+
+/// How particles move from one scene's text to the next.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Transition {
+    /// Particles flow toward the next scene's text over the whole scene, and
+    /// the background crossfades to its colors.
+    #[default]
+    Morph,
+    /// Particles and background hold this scene, then jump to the next.
+    Cut,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scene {
+    pub text: String,
+    /// Background color, RGB.
+    pub color: [f32; 3],
+    /// Particle color, RGB. Defaults to `color` brightened to full value.
+    #[serde(default)]
+    pub glow: Option<[f32; 3]>,
+    /// Seconds the scene lasts, including its transition to the next scene.
+    #[serde(default = "default_duration")]
+    pub duration: f32,
+    #[serde(default)]
+    pub transition: Transition,
+    /// Easing of the particle morph toward the next scene, by name.
+    #[serde(default = "default_easing")]
+    pub easing: Easing,
+}
+
+fn default_duration() -> f32 {
+    4.0
+}
+
+fn default_easing() -> Easing {
+    Easing::InOut(Curve::Expo)
+}
+
+impl Scene {
+    /// How far the particles have morphed toward the next scene, `seconds`
+    /// into this one.
+    pub fn morph_progress(&self, seconds: f32) -> f32 {
+        match self.transition {
+            Transition::Morph => self.easing.apply(seconds / self.duration),
+            Transition::Cut => 0.0,
+        }
+    }
+
+    /// Particle color, RGB.
+    pub fn glow(&self) -> [f32; 3] {
+        self.glow.unwrap_or_else(|| {
+            let [r, g, b] = self.color;
+            let peak = r.max(g).max(b).max(1e-3);
+            [r / peak, g / peak, b / peak]
+        })
+    }
+}
+
+/// A reel: scenes shown in order, looping back to the first after the last.
+/// Written in TOML:
+///
+/// ```toml
+/// [[scene]]
+/// text = "I miss you."
+/// color = [0.4, 0.1, 0.2]
+/// duration = 4.0          # seconds, default 4
+/// transition = "morph"    # or "cut"
+/// easing = "expo-in-out"  # any `Easing`, e.g. "back-out", "steps(4)" or
+///                         # "cubic-bezier(0.4, 0, 0.2, 1)"
+/// ```
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Script {
+    #[serde(rename = "scene", default)]
+    pub scenes: Vec<Scene>,
+}
+
+#[derive(Debug)]
+pub enum ScriptError {
+    Io(std::io::Error),
+    Parse(toml::de::Error),
+    /// The script has no `[[scene]]` entries.
+    NoScenes,
+    /// Scene `scene` (counting from 0) has no text to spell out.
+    EmptyText {
+        scene: usize,
+    },
+    /// Scene `scene` (counting from 0) has a duration that is not a positive
+    /// number of seconds.
+    BadDuration {
+        scene: usize,
+        duration: f32,
+    },
+}
+
+impl std::fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScriptError::Io(err) => write!(f, "cannot read script: {err}"),
+            ScriptError::Parse(err) => write!(f, "invalid script: {err}"),
+            ScriptError::NoScenes => write!(f, "script has no scenes"),
+            ScriptError::EmptyText { scene } => write!(f, "scene {scene}: text is empty"),
+            ScriptError::BadDuration { scene, duration } => {
+                write!(
+                    f,
+                    "scene {scene}: duration must be positive, got {duration}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScriptError {}
+
+impl Script {
+    pub fn parse(text: &str) -> Result<Script, ScriptError> {
+        let script: Script = toml::from_str(text).map_err(ScriptError::Parse)?;
+        if script.scenes.is_empty() {
+            return Err(ScriptError::NoScenes);
+        }
+        for (scene, s) in script.scenes.iter().enumerate() {
+            if s.text.trim().is_empty() {
+                return Err(ScriptError::EmptyText { scene });
+            }
+            if !(s.duration > 0.0 && s.duration.is_finite()) {
+                return Err(ScriptError::BadDuration {
+                    scene,
+                    duration: s.duration,
+                });
+            }
+        }
+        Ok(script)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Script, ScriptError> {
+        Script::parse(&std::fs::read_to_string(path).map_err(ScriptError::Io)?)
+    }
+
+    /// Scene indices, one after another, looping.
+    pub fn timeline(&self) -> Timeline<usize> {
+        let mut timeline = Timeline::new().looping(true);
+        for (idx, scene) in self.scenes.iter().enumerate() {
+            timeline.then(scene.duration, idx);
+        }
+        timeline
+    }
+
+    /// Index of the scene after `idx`, looping.
+    pub fn next(&self, idx: usize) -> usize {
+        (idx + 1) % self.scenes.len()
+    }
+
+    /// Every character any scene shows.
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.scenes.iter().flat_map(|s| s.text.chars())
+    }
+}
@@ -0,0 +1,70 @@
+// Draws each particle as a soft, anti-aliased disc, one instance per particle,
+// optionally smeared into a streak along its velocity.
+
+struct View {
+    params: vec4<f32>, // xy: resolution, z: radius in pixels, w: softness
+    tint: vec4<f32>,   // multiplied into every particle's color
+    motion: vec4<f32>, // x: seconds of motion drawn as a streak
+};
+
+@group(0) @binding(0) var<uniform> view: View;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    // From the particle center in pixels, along and across the velocity.
+    @location(0) local: vec2<f32>,
+    @location(1) streak: f32, // streak length in pixels, behind the center
+    @location(2) color: vec4<f32>,
+};
+
+@vertex
+fn vs_main(
+    @builtin(vertex_index) idx: u32,
+    @location(0) particle: vec4<f32>, // xy: position, zw: velocity
+    @location(1) color: vec4<f32>,
+) -> VertexOutput {
+    var corners = array<vec2<f32>, 6>(
+        vec2(-1.0, -1.0), vec2(1.0, -1.0), vec2(-1.0, 1.0),
+        vec2(-1.0, 1.0), vec2(1.0, -1.0), vec2(1.0, 1.0),
+    );
+    let corner = corners[idx];
+    // One pixel of padding leaves room for the anti-aliased edge and for
+    // bilinear splats of sub-pixel discs.
+    let r = view.params.z + 1.0;
+    let speed = length(particle.zw);
+    let streak = speed * view.motion.x;
+    let dir = select(vec2<f32>(1.0, 0.0), particle.zw / speed, speed > 1e-4);
+    let local = vec2<f32>(select(r, -streak - r, corner.x < 0.0), corner.y * r);
+    let offset = dir * local.x + vec2<f32>(-dir.y, dir.x) * local.y;
+    let ndc = (particle.xy + offset) / view.params.xy * 2.0 - 1.0;
+
+    var out: VertexOutput;
+    out.position = vec4<f32>(ndc.x, -ndc.y, 0.0, 1.0);
+    out.local = local;
+    out.streak = streak;
+    // Spreading the same light over a longer streak keeps brightness even.
+    let width = 2.0 * max(view.params.z, 0.5);
+    out.color = color * view.tint * vec4<f32>(1.0, 1.0, 1.0, width / (width + streak));
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let radius = view.params.z;
+    // Offset to the nearest point of the streak's center line.
+    let offset = vec2<f32>(in.local.x - clamp(in.local.x, -in.streak, 0.0), in.local.y);
+    var coverage: f32;
+    if radius < 1.0 {
+        // Too small to rasterize as a disc: spread its area over the nearest
+        // pixels with bilinear weights so it moves smoothly between them.
+        let w = max(vec2<f32>(0.0), 1.0 - abs(offset));
+        coverage = w.x * w.y * min(3.14159265 * radius * radius, 1.0);
+    } else {
+        let d = length(offset);
+        let edge = clamp(radius + 0.5 - d, 0.0, 1.0);
+        let falloff = 1.0 - smoothstep(radius * (1.0 - view.params.w), radius, d);
+        coverage = edge * falloff;
+    }
+    let alpha = in.color.a * coverage;
+    return vec4<f32>(in.color.rgb * alpha, alpha);
+}
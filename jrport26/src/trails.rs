@@ -0,0 +1,302 @@
+/// How a reel's trails look. Applied every frame by `Trails::render`.
+#[derive(Clone, Copy, Debug)]
+pub struct TrailSettings {
+    /// Seconds for a trail to fade to half brightness; 0 disables trails.
+    pub half_life: f32,
+    /// Additive ghosting: how much of a blurred copy of the history is added
+    /// back per sixtieth of a second, from 0 up to (not including) 1.
+    /// `Trails::render` turns it into a gain for the actual frame length.
+    pub ghost: f32,
+    /// How fast trails drift, in pixels per second.
+    pub drift: (f32, f32),
+    /// Hue rotation of the history, in radians per second.
+    pub hue_shift: f32,
+}
+
+impl Default for TrailSettings {
+    fn default() -> Self {
+        Self {
+            half_life: 0.04,
+            ghost: 0.0,
+            drift: (0.0, 0.0),
+            hue_shift: 0.0,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+struct FeedbackUniforms {
+    params: [f32; 4], // [fade, ghost, hue rotation, 0]
+    drift: [f32; 4],  // [drift u, drift v, texel width, texel height]
+}
+
+struct Layer {
+    view: wgpu::TextureView,
+    /// Samples this layer.
+    bind_group: wgpu::BindGroup,
+}
+
+/// Most a frame may keep of the last one, fade and ghosting together. Any
+/// more and the feedback loop would brighten to white instead of fading.
+const MAX_FEEDBACK: f32 = 0.999;
+
+/// A pair of offscreen layers that particles are drawn into. Each frame the
+/// previous layer is faded into the other one (ping-pong) before new
+/// particles are drawn, so trails cost one fullscreen pass on the GPU
+/// regardless of resolution.
+pub struct Trails {
+    pub settings: TrailSettings,
+    size: (u32, u32),
+    layers: [Layer; 2],
+    current: usize,
+    uniforms: wgpu::Buffer,
+    sampler: wgpu::Sampler,
+    bind_group_layout: wgpu::BindGroupLayout,
+    feedback_pipeline: wgpu::RenderPipeline,
+    composite_pipeline: wgpu::RenderPipeline,
+}
+
+impl Trails {
+    /// Format of the layers; draw particles with pipelines built for it.
+    pub const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
+
+    /// Creates `width` x `height` layers that composite onto targets of
+    /// `output_format`.
+    pub fn new(
+        device: &wgpu::Device,
+        output_format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        let uniforms = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Trail uniforms"),
+            size: std::mem::size_of::<FeedbackUniforms>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Trail sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Trails"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("trails.wgsl"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("trails.wgsl").into()),
+        });
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            bind_group_layouts: &[&bind_group_layout],
+            ..Default::default()
+        });
+        let pipeline = |label: &str, entry_point: &str, format, blend| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(&layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some("vs_main"),
+                    buffers: &[],
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some(entry_point),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format,
+                        blend,
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: Default::default(),
+                }),
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview_mask: None,
+                cache: None,
+            })
+        };
+        let feedback_pipeline = pipeline("Trail feedback", "fs_feedback", Self::FORMAT, None);
+        let composite_pipeline = pipeline(
+            "Trail composite",
+            "fs_composite",
+            output_format,
+            Some(wgpu::BlendState {
+                color: wgpu::BlendComponent {
+                    src_factor: wgpu::BlendFactor::One,
+                    dst_factor: wgpu::BlendFactor::One,
+                    operation: wgpu::BlendOperation::Add,
+                },
+                alpha: wgpu::BlendComponent::OVER,
+            }),
+        );
+
+        let layers = Self::create_layers(
+            device,
+            &bind_group_layout,
+            &uniforms,
+            &sampler,
+            width,
+            height,
+        );
+        Self {
+            settings: TrailSettings::default(),
+            size: (width.max(1), height.max(1)),
+            layers,
+            current: 0,
+            uniforms,
+            sampler,
+            bind_group_layout,
+            feedback_pipeline,
+            composite_pipeline,
+        }
+    }
+
+    fn create_layers(
+        device: &wgpu::Device,
+        bind_group_layout: &wgpu::BindGroupLayout,
+        uniforms: &wgpu::Buffer,
+        sampler: &wgpu::Sampler,
+        width: u32,
+        height: u32,
+    ) -> [Layer; 2] {
+        std::array::from_fn(|_| {
+            let texture = device.create_texture(&wgpu::TextureDescriptor {
+                label: Some("Trail layer"),
+                size: wgpu::Extent3d {
+                    width: width.max(1),
+                    height: height.max(1),
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: Self::FORMAT,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            });
+            let view = texture.create_view(&Default::default());
+            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Trail layer"),
+                layout: bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: uniforms.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(&view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::Sampler(sampler),
+                    },
+                ],
+            });
+            Layer { view, bind_group }
+        })
+    }
+
+    /// Recreates the layers at a new size, dropping existing trails.
+    pub fn resize(&mut self, device: &wgpu::Device, width: u32, height: u32) {
+        self.layers = Self::create_layers(
+            device,
+            &self.bind_group_layout,
+            &self.uniforms,
+            &self.sampler,
+            width,
+            height,
+        );
+        self.size = (width.max(1), height.max(1));
+        self.current = 0;
+    }
+
+    /// Fades the previous frame `dt` seconds into the next layer, then lets
+    /// `draw` add this frame's particles on top.
+    pub fn render(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        queue: &wgpu::Queue,
+        dt: f32,
+        draw: impl FnOnce(&mut wgpu::RenderPass<'_>),
+    ) {
+        let s = &self.settings;
+        let fade = if s.half_life > 0.0 {
+            0.5f32.powf(dt / s.half_life)
+        } else {
+            0.0
+        };
+        // Compound the ghosting like the fade, so both keep their look
+        // whatever the frame rate.
+        let ghost = 1.0 - (1.0 - s.ghost.clamp(0.0, 0.99)).powf(dt * 60.0);
+        let fade = fade.min(MAX_FEEDBACK / (1.0 + ghost));
+        let (w, h) = (self.size.0 as f32, self.size.1 as f32);
+        let uniforms = FeedbackUniforms {
+            params: [fade, ghost, s.hue_shift * dt, 0.0],
+            drift: [s.drift.0 * dt / w, s.drift.1 * dt / h, 1.0 / w, 1.0 / h],
+        };
+        queue.write_buffer(&self.uniforms, 0, bytemuck::bytes_of(&uniforms));
+
+        let next = 1 - self.current;
+        let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Trails"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &self.layers[next].view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            ..Default::default()
+        });
+        rpass.set_pipeline(&self.feedback_pipeline);
+        rpass.set_bind_group(0, &self.layers[self.current].bind_group, &[]);
+        rpass.draw(0..3, 0..1);
+        draw(&mut rpass);
+        drop(rpass);
+        self.current = next;
+    }
+
+    /// Adds the latest layer onto a target of the output format.
+    pub fn composite(&self, rpass: &mut wgpu::RenderPass<'_>) {
+        rpass.set_pipeline(&self.composite_pipeline);
+        rpass.set_bind_group(0, &self.layers[self.current].bind_group, &[]);
+        rpass.draw(0..3, 0..1);
+    }
+}
@@ -0,0 +1,53 @@
+// Frame feedback for particle trails: each frame starts from the previous one,
+// faded, drifted, ghosted and hue-shifted, before new particles are drawn on
+// top. A second entry point composites the result onto the output.
+
+struct Feedback {
+    params: vec4<f32>, // x: fade this frame, y: ghost gain, z: hue rotation this frame
+    drift: vec4<f32>,  // xy: drift this frame in uv, zw: texel size
+};
+
+@group(0) @binding(0) var<uniform> feedback: Feedback;
+@group(0) @binding(1) var layer: texture_2d<f32>;
+@group(0) @binding(2) var layer_sampler: sampler;
+
+@vertex
+fn vs_main(@builtin(vertex_index) idx: u32) -> @builtin(position) vec4<f32> {
+    var pos = array<vec2<f32>, 3>(vec2(-1.0, -1.0), vec2(3.0, -1.0), vec2(-1.0, 3.0));
+    return vec4<f32>(pos[idx], 0.0, 1.0);
+}
+
+// Rotates `c` around the grey axis, which shifts hue but keeps brightness.
+fn hue_rotate(c: vec3<f32>, angle: f32) -> vec3<f32> {
+    let k = vec3<f32>(0.57735027);
+    let cos_a = cos(angle);
+    return c * cos_a + cross(k, c) * sin(angle) + k * dot(k, c) * (1.0 - cos_a);
+}
+
+@fragment
+fn fs_feedback(@builtin(position) clip_pos: vec4<f32>) -> @location(0) vec4<f32> {
+    let texel = feedback.drift.zw;
+    let uv = clip_pos.xy * texel - feedback.drift.xy;
+    if any(uv < vec2<f32>(0.0)) || any(uv > vec2<f32>(1.0)) {
+        return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+    }
+
+    var color = textureSampleLevel(layer, layer_sampler, uv, 0.0).rgb;
+    if feedback.params.y > 0.0 {
+        // Additive ghosting: a blurred copy of the history glows around it.
+        let d = texel * 1.5;
+        let blurred = textureSampleLevel(layer, layer_sampler, uv + vec2(d.x, d.y), 0.0).rgb
+            + textureSampleLevel(layer, layer_sampler, uv + vec2(-d.x, d.y), 0.0).rgb
+            + textureSampleLevel(layer, layer_sampler, uv + vec2(d.x, -d.y), 0.0).rgb
+            + textureSampleLevel(layer, layer_sampler, uv + vec2(-d.x, -d.y), 0.0).rgb;
+        color += feedback.params.y * 0.25 * blurred;
+    }
+    color = hue_rotate(color, feedback.params.z) * feedback.params.x;
+    return vec4<f32>(max(color, vec3<f32>(0.0)), 1.0);
+}
+
+@fragment
+fn fs_composite(@builtin(position) clip_pos: vec4<f32>) -> @location(0) vec4<f32> {
+    let uv = clip_pos.xy * feedback.drift.zw;
+    return vec4<f32>(textureSampleLevel(layer, layer_sampler, uv, 0.0).rgb, 1.0);
+}
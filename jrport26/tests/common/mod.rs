@@ -0,0 +1,38 @@
+#![allow(dead_code)]
+
+use jrport26::headless::Headless;
+
+/// Renders at `width` x `height` on the adapter picked by the `WGPU_*`
+/// environment variables, or the default one. Tests that need it are ignored
+/// by default; any adapter will do, and `WGPU_BACKEND=gl cargo test --
+/// --ignored` runs them on Mesa's llvmpipe where there is no GPU. Panics
+/// without an adapter, so an opted-in run can't pass vacuously.
+pub fn headless(width: u32, height: u32) -> Headless {
+    Headless::new(width, height).unwrap()
+}
+
+/// A `size` x `size` frame that reads back the values shaders wrote, without
+/// sRGB encoding.
+pub fn linear_frame(size: u32) -> Headless {
+    headless(size, size).with_format(wgpu::TextureFormat::Rgba8Unorm)
+}
+
+/// Runs `draw` in a render pass over `frame`, cleared to black, and returns
+/// its rows of pixels.
+pub fn render_pass(frame: &Headless, draw: impl FnOnce(&mut wgpu::RenderPass<'_>)) -> Vec<u8> {
+    frame.render_with(|encoder, view| {
+        let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            ..Default::default()
+        });
+        draw(&mut rpass);
+    })
+}
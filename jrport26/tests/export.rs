@@ -0,0 +1,105 @@
+use jrport26::export::{ExportFormat, Exporter};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+const WIDTH: u32 = 8;
+const HEIGHT: u32 = 4;
+
+fn out(name: &str) -> PathBuf {
+    let path = Path::new(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let _ = std::fs::remove_dir_all(&path);
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+/// A frame lit up to column `n`, with the alpha a reel might leave behind.
+fn frame(n: u32) -> Vec<u8> {
+    (0..WIDTH * HEIGHT)
+        .flat_map(|i| {
+            if i % WIDTH <= n {
+                [255, 128, 0, 7]
+            } else {
+                [0, 0, 0, 0]
+            }
+        })
+        .collect()
+}
+
+fn export(path: &Path, format: ExportFormat, frames: u32) {
+    let mut exporter = Exporter::create(path, format, WIDTH, HEIGHT, 25.0, frames).unwrap();
+    for n in 0..frames {
+        exporter.write(&frame(n)).unwrap();
+    }
+    exporter.finish().unwrap();
+}
+
+#[test]
+fn formats_follow_the_extension() {
+    let format = |path: &str| ExportFormat::from_path(Path::new(path));
+    assert_eq!(format("reel.gif"), ExportFormat::Gif);
+    assert_eq!(format("reel.APNG"), ExportFormat::Apng);
+    assert_eq!(format("reel.mp4"), ExportFormat::Video);
+    assert_eq!(format("reel.webm"), ExportFormat::Video);
+    assert_eq!(format("frames"), ExportFormat::PngSequence);
+    assert_eq!("apng".parse(), Ok(ExportFormat::Apng));
+    assert!("jpeg".parse::<ExportFormat>().is_err());
+}
+
+#[test]
+fn png_sequences_are_numbered_opaque_frames() {
+    let dir = out("sequence");
+    export(&dir, ExportFormat::PngSequence, 3);
+    for n in 0..3 {
+        let file = File::open(dir.join(format!("frame_{n:05}.png"))).unwrap();
+        let mut reader = png::Decoder::new(std::io::BufReader::new(file))
+            .read_info()
+            .unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size().unwrap()];
+        reader.next_frame(&mut pixels).unwrap();
+        let mut expected = frame(n);
+        expected.chunks_mut(4).for_each(|px| px[3] = 255);
+        assert_eq!(pixels, expected);
+    }
+    assert!(!dir.join("frame_00003.png").exists());
+}
+
+#[test]
+fn apngs_hold_every_frame() {
+    let path = out("reel.apng");
+    export(&path, ExportFormat::Apng, 4);
+    let reader = png::Decoder::new(std::io::BufReader::new(File::open(&path).unwrap()))
+        .read_info()
+        .unwrap();
+    let animation = reader.info().animation_control.unwrap();
+    assert_eq!(animation.num_frames, 4);
+    assert_eq!(animation.num_plays, 0);
+    let delay = reader.info().frame_control.unwrap();
+    assert_eq!((delay.delay_num, delay.delay_den), (100, 2500));
+}
+
+#[test]
+fn gifs_loop_at_the_frame_rate() {
+    let path = out("reel.gif");
+    export(&path, ExportFormat::Gif, 3);
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::RGBA);
+    let mut decoder = options.read_info(File::open(&path).unwrap()).unwrap();
+    assert_eq!(
+        (decoder.width(), decoder.height()),
+        (WIDTH as u16, HEIGHT as u16)
+    );
+    let mut delays = Vec::new();
+    while let Some(frame) = decoder.read_next_frame().unwrap() {
+        delays.push(frame.delay);
+    }
+    assert_eq!(delays, [4, 4, 4]);
+}
+
+/// Run with `cargo test -- --ignored` where `ffmpeg` is on the `PATH`.
+#[test]
+#[ignore = "needs ffmpeg"]
+fn videos_go_through_ffmpeg() {
+    let path = out("reel.mp4");
+    export(&path, ExportFormat::Video, 5);
+    assert!(std::fs::metadata(&path).unwrap().len() > 0);
+}
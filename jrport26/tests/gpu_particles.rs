@@ -0,0 +1,133 @@
+mod common;
+
+use commons::easing::{Curve, Easing};
+use commons::physics::{Attractor, Particle, PhysicsParams, step_particles};
+use jrport26::headless::Headless;
+use jrport26::particles::{GpuParticles, SpriteBlend, SpriteStyle};
+
+/// Draws `gpu` into `frame` and returns its rows of pixels.
+fn render(frame: &Headless, gpu: &GpuParticles) -> Vec<u8> {
+    let (width, height) = frame.size();
+    gpu.set_view(frame.queue(), width as f32, height as f32);
+    common::render_pass(frame, |rpass| gpu.draw(rpass))
+}
+
+#[test]
+#[ignore = "needs a wgpu adapter"]
+fn matches_cpu_physics() {
+    let headless = common::headless(1, 1);
+    let (device, queue) = (headless.device(), headless.queue());
+
+    let start: Vec<Particle> = (0..1000)
+        .map(|i| Particle::at((i % 40) as f32 * 10.0, (i / 40) as f32 * 10.0))
+        .collect();
+    let from: Vec<(f32, f32)> = (0..1000).map(|i| (i as f32 * 0.3, 100.0)).collect();
+    let to: Vec<(f32, f32)> = (0..1000).map(|i| (300.0, i as f32 * 0.2)).collect();
+    let params = PhysicsParams {
+        gravity: (0.0, 30.0),
+        wind: (10.0, 0.0),
+        drag: 0.5,
+        attractors: vec![Attractor {
+            x: 150.0,
+            y: 100.0,
+            strength: -800.0,
+            radius: 80.0,
+        }],
+        ..Default::default()
+    };
+
+    let mut gpu = GpuParticles::new(device, wgpu::TextureFormat::Rgba8Unorm, &start);
+    gpu.set_targets(queue, &from, &to);
+    let easing = Easing::InOut(Curve::Expo);
+    gpu.set_morph(queue, 0.5, 4.0, |t| easing.apply(t));
+    let step = 1.0 / 120.0;
+    // Two batches, as a frame that spans a quote change would submit.
+    gpu.simulate(device, queue, &params, step, 100, step);
+    gpu.simulate(device, queue, &params, 101.0 * step, 140, step);
+    let on_gpu = gpu.read_particles(device, queue);
+
+    let mut on_cpu = start.clone();
+    for k in 1..=240 {
+        let time = k as f32 * step;
+        let t = easing.apply((time - 0.5) / 4.0);
+        let target = |i: usize| {
+            (
+                from[i].0 + (to[i].0 - from[i].0) * t,
+                from[i].1 + (to[i].1 - from[i].1) * t,
+            )
+        };
+        step_particles(&mut on_cpu, target, &params, time, step);
+    }
+
+    assert_eq!(on_gpu.len(), on_cpu.len());
+    for (g, c) in on_gpu.iter().zip(&on_cpu) {
+        assert!(
+            (g.x - c.x).abs() < 0.05 && (g.y - c.y).abs() < 0.05,
+            "{g:?} vs {c:?}"
+        );
+    }
+}
+
+#[test]
+#[ignore = "needs a wgpu adapter"]
+fn curl_noise_stays_finite() {
+    let headless = common::headless(1, 1);
+    let (device, queue) = (headless.device(), headless.queue());
+
+    let start = vec![Particle::at(200.0, 200.0); 300_000];
+    let params = PhysicsParams {
+        noise_strength: 50.0,
+        curl_strength: 400.0,
+        ..Default::default()
+    };
+    let gpu = GpuParticles::new(device, wgpu::TextureFormat::Rgba8Unorm, &start);
+    gpu.set_targets(queue, &[(100.0, 100.0)], &[(300.0, 300.0)]);
+    gpu.simulate(device, queue, &params, 0.0, 60, 1.0 / 60.0);
+
+    let particles = gpu.read_particles(device, queue);
+    assert_eq!(particles.len(), 300_000);
+    assert!(particles.iter().all(|p| p.x.is_finite() && p.y.is_finite()));
+    assert!(particles.iter().any(|p| (p.x - 200.0).abs() > 1.0));
+}
+
+#[test]
+#[ignore = "needs a wgpu adapter"]
+fn splats_soft_tinted_discs() {
+    let frame = common::linear_frame(16);
+    let (device, queue) = (frame.device(), frame.queue());
+
+    let mut gpu = GpuParticles::new(device, frame.format(), &[Particle::at(8.0, 8.0)]);
+    gpu.set_colors(queue, &[[1.0, 0.5, 1.0, 1.0]]);
+    gpu.style = SpriteStyle {
+        radius: 4.0,
+        softness: 0.5,
+        blend: SpriteBlend::Alpha,
+        tint: [1.0, 1.0, 0.0, 1.0],
+        ..Default::default()
+    };
+    let pixels = render(&frame, &gpu);
+    let at = |x: usize, y: usize| &pixels[(y * 16 + x) * 4..(y * 16 + x) * 4 + 4];
+
+    // Pixel centers sit at half coordinates, so the disc straddles 7 and 8.
+    assert_eq!(at(7, 7)[..3], [255, 128, 0]);
+    assert_eq!(at(7, 7), at(8, 8));
+    assert_eq!(at(4, 7), at(11, 7));
+    assert!(at(5, 7)[0] > at(4, 7)[0] && at(4, 7)[0] > 0);
+    assert_eq!(at(0, 0), [0, 0, 0, 255]);
+}
+
+#[test]
+#[ignore = "needs a wgpu adapter"]
+fn sub_pixel_particles_splat_bilinearly() {
+    let frame = common::linear_frame(8);
+
+    // A quarter pixel right of the center of pixel (4, 4).
+    let mut gpu = GpuParticles::new(frame.device(), frame.format(), &[Particle::at(4.75, 4.5)]);
+    gpu.style.radius = 1.0 / std::f32::consts::PI.sqrt() - 1e-4;
+    let pixels = render(&frame, &gpu);
+    let red = |x: usize, y: usize| pixels[(y * 8 + x) * 4] as i32;
+
+    assert!((red(4, 4) - 191).abs() <= 2, "{}", red(4, 4));
+    assert!((red(5, 4) - 64).abs() <= 2, "{}", red(5, 4));
+    assert_eq!(red(3, 4) + red(4, 3) + red(4, 5), 0);
+}
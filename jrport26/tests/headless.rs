@@ -0,0 +1,135 @@
+mod common;
+
+use commons::clock::Clock;
+use jrport26::headless::Headless;
+use jrport26::reel::Reel;
+use jrport26::reelone::ReelOne;
+use jrport26::reeltwo::ReelTwo;
+use jrport26::script::Script;
+
+const WIDTH: u32 = 320;
+const HEIGHT: u32 = 180;
+
+fn headless() -> Headless {
+    common::headless(WIDTH, HEIGHT)
+}
+
+fn reel_one(headless: &Headless) -> ReelOne {
+    ReelOne::new(
+        headless.device(),
+        headless.queue(),
+        Headless::FORMAT,
+        WIDTH,
+        HEIGHT,
+        Script::parse(include_str!("../src/bin/reelone.toml")).unwrap(),
+    )
+}
+
+/// Plays `frames` frames at 30 fps and returns the last one.
+fn last_frame(headless: &Headless, reel: &mut dyn Reel, frames: u32) -> Vec<u8> {
+    let mut last = Vec::new();
+    headless
+        .run(reel, 30.0, frames, |_, frame| {
+            last = frame.to_vec();
+            Ok::<_, ()>(())
+        })
+        .unwrap();
+    last
+}
+
+fn lit(frame: &[u8]) -> usize {
+    frame
+        .chunks(4)
+        .filter(|px| px[..3].iter().any(|&c| c > 8))
+        .count()
+}
+
+#[test]
+#[ignore = "needs a wgpu adapter"]
+fn frames_are_tightly_packed_rgba() {
+    let headless = headless();
+    let mut reel = ReelTwo::new(headless.device(), Headless::FORMAT, WIDTH, HEIGHT);
+    let frame = last_frame(&headless, &mut reel, 1);
+    assert_eq!(frame.len(), (WIDTH * HEIGHT * 4) as usize);
+}
+
+#[test]
+#[ignore = "needs a wgpu adapter"]
+fn reel_one_renders_the_same_frames_every_run() {
+    let headless = headless();
+    let first = last_frame(&headless, &mut reel_one(&headless), 45);
+    let second = last_frame(&headless, &mut reel_one(&headless), 45);
+    assert!(lit(&first) > 0, "frame is black");
+    assert!(first == second, "headless runs differ");
+}
+
+#[test]
+#[ignore = "needs a wgpu adapter"]
+fn reel_one_loops_over_its_script() {
+    let headless = headless();
+    assert_eq!(reel_one(&headless).duration(), Some(60.0));
+}
+
+#[test]
+#[ignore = "needs a wgpu adapter"]
+fn reel_two_animates() {
+    let headless = headless();
+    let mut reel = ReelTwo::new(headless.device(), Headless::FORMAT, WIDTH, HEIGHT);
+    let early = last_frame(&headless, &mut reel, 1);
+    let later = last_frame(&headless, &mut reel, 30);
+    assert!(lit(&later) > 0, "frame is black");
+    assert!(early != later, "reel two is frozen");
+}
+
+#[test]
+#[ignore = "needs a wgpu adapter"]
+fn run_stops_at_the_first_error() {
+    let headless = headless();
+    let mut reel = ReelTwo::new(headless.device(), Headless::FORMAT, WIDTH, HEIGHT);
+    let mut seen = Vec::new();
+    let result = headless.run(&mut reel, 30.0, 10, |index, _| {
+        seen.push(index);
+        if index == 2 { Err(index) } else { Ok(()) }
+    });
+    assert_eq!(result, Err(2));
+    assert_eq!(seen, [0, 1, 2]);
+}
+
+#[test]
+#[ignore = "needs a wgpu adapter"]
+fn paused_reels_hold_their_frame() {
+    let headless = headless();
+    let mut reel = reel_one(&headless);
+    let mut clock = Clock::manual();
+    for _ in 0..20 {
+        clock.advance(1.0 / 30.0);
+        reel.update(headless.device(), headless.queue(), &clock);
+    }
+    clock.set_paused(true);
+    let frames: Vec<_> = (0..3)
+        .map(|_| {
+            clock.advance(1.0 / 30.0);
+            reel.update(headless.device(), headless.queue(), &clock);
+            headless.render(&mut reel)
+        })
+        .collect();
+    assert!(lit(&frames[0]) > 0, "frame is black");
+    assert!(
+        frames[0] == frames[1] && frames[1] == frames[2],
+        "paused frames differ"
+    );
+}
+
+#[test]
+#[ignore = "needs a wgpu adapter"]
+fn reels_follow_a_resize() {
+    let mut headless = headless();
+    let mut reel = reel_one(&headless);
+    last_frame(&headless, &mut reel, 10);
+    headless.resize(200, 120);
+    reel.resize(headless.device(), headless.queue(), 200, 120);
+    assert_eq!(headless.size(), (200, 120));
+    let frame = last_frame(&headless, &mut reel, 10);
+    assert_eq!(frame.len(), 200 * 120 * 4);
+    assert!(lit(&frame) > 0, "frame is black");
+}
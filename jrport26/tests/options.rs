@@ -0,0 +1,109 @@
+use jrport26::display::{MonitorChoice, WindowMode};
+use jrport26::export::ExportFormat;
+use jrport26::options::{Options, OptionsError};
+use std::path::PathBuf;
+
+fn parse(args: &[&str]) -> Result<Options, OptionsError> {
+    Options::parse(args.iter().map(|arg| arg.to_string()))
+}
+
+#[test]
+fn defaults_to_a_window_at_sixty_fps() {
+    assert_eq!(parse(&[]).unwrap(), Options::default());
+    assert_eq!(Options::default().fps, 60.0);
+}
+
+#[test]
+fn reads_headless_options_and_script() {
+    let options = parse(&["--headless", "--size", "640x360", "--fps=30", "reel.toml"]).unwrap();
+    assert!(options.headless);
+    assert_eq!(options.size, Some((640, 360)));
+    assert_eq!(options.fps, 30.0);
+    assert_eq!(options.script, Some(PathBuf::from("reel.toml")));
+}
+
+#[test]
+fn exporting_implies_headless() {
+    let options = parse(&["--export", "out/reel.png", "--format", "apng"]).unwrap();
+    assert!(options.headless);
+    assert_eq!(options.export, Some(PathBuf::from("out/reel.png")));
+    assert_eq!(options.format, Some(ExportFormat::Apng));
+    assert_eq!(
+        parse(&["--format", "jpeg"]),
+        Err(OptionsError::BadValue {
+            option: "--format",
+            value: "jpeg".into()
+        })
+    );
+}
+
+#[test]
+fn frames_cover_the_reel_unless_seconds_are_given() {
+    let options = parse(&["--fps", "30"]).unwrap();
+    assert_eq!(options.frames(Some(60.0)), 1800);
+    assert_eq!(options.frames(None), 300);
+    let options = parse(&["--fps", "30", "--seconds", "2.5"]).unwrap();
+    assert_eq!(options.frames(Some(60.0)), 75);
+}
+
+#[test]
+fn rejects_bad_arguments() {
+    assert_eq!(
+        parse(&["--size", "640"]),
+        Err(OptionsError::BadValue {
+            option: "--size",
+            value: "640".into()
+        })
+    );
+    assert_eq!(
+        parse(&["--fps", "0"]),
+        Err(OptionsError::BadValue {
+            option: "--fps",
+            value: "0".into()
+        })
+    );
+    assert_eq!(parse(&["--fps"]), Err(OptionsError::MissingValue("--fps")));
+    assert_eq!(
+        parse(&["--fullscreen"]),
+        Err(OptionsError::UnknownOption("--fullscreen".into()))
+    );
+    assert_eq!(
+        parse(&["a.toml", "b.toml"]),
+        Err(OptionsError::ExtraArgument("b.toml".into()))
+    );
+}
+
+#[test]
+fn monitors_are_picked_by_index_or_name() {
+    let options = parse(&["--monitor", "HDMI-1", "--mode", "exclusive"]).unwrap();
+    assert_eq!(options.monitor, Some(MonitorChoice::Name("HDMI-1".into())));
+    assert_eq!(options.mode, Some(WindowMode::Exclusive));
+    assert_eq!(
+        parse(&["--monitor=1"]).unwrap().monitor,
+        Some(MonitorChoice::Index(1))
+    );
+    assert!(parse(&["--list-monitors"]).unwrap().list_monitors);
+    assert!(parse(&["--mode", "fullscreen"]).is_err());
+
+    let names = || [Some("eDP-1".to_string()), None, Some("HDMI-1".to_string())];
+    assert_eq!(MonitorChoice::Name("HDMI-1".into()).find(names()), Some(2));
+    assert_eq!(MonitorChoice::Index(1).find(names()), Some(1));
+    assert_eq!(MonitorChoice::Index(3).find(names()), None);
+    assert_eq!(MonitorChoice::Name("DP-2".into()).find(names()), None);
+}
+
+#[test]
+fn config_fills_in_what_the_command_line_leaves_out() {
+    let config = "monitor = 2\nmode = \"windowed\"\nsize = [1280, 720]\n";
+    let mut options = parse(&["--size", "800x600"]).unwrap();
+    options.apply_config(config).unwrap();
+    assert_eq!(options.monitor, Some(MonitorChoice::Index(2)));
+    assert_eq!(options.mode, Some(WindowMode::Windowed));
+    assert_eq!(options.size, Some((800, 600)));
+
+    let mut options = parse(&[]).unwrap();
+    assert!(options.apply_config("monitr = 2\n").is_err());
+    options.apply_config("monitor = \"eDP-1\"\n").unwrap();
+    assert_eq!(options.monitor, Some(MonitorChoice::Name("eDP-1".into())));
+    assert_eq!(options.mode, None);
+}
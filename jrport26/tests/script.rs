@@ -0,0 +1,112 @@
+use commons::easing::{Curve, Easing};
+use jrport26::script::{Script, ScriptError, Transition};
+
+#[test]
+fn default_reel_parses() {
+    let script = Script::parse(include_str!("../src/bin/reelone.toml")).unwrap();
+    assert_eq!(script.scenes.len(), 15);
+    assert_eq!(script.scenes[0].text, "I miss you.");
+    assert_eq!(script.scenes[0].color, [0.4, 0.1, 0.2]);
+    assert_eq!(script.timeline().duration(), 60.0);
+}
+
+#[test]
+fn scenes_default_to_four_second_expo_morphs() {
+    let script = Script::parse("[[scene]]\ntext = \"a\"\ncolor = [0.5, 0.25, 0.0]\n").unwrap();
+    let scene = &script.scenes[0];
+    assert_eq!(scene.duration, 4.0);
+    assert_eq!(scene.transition, Transition::Morph);
+    assert_eq!(scene.easing, Easing::InOut(Curve::Expo));
+    assert_eq!(scene.glow(), [1.0, 0.5, 0.0]);
+    assert_eq!(scene.morph_progress(2.0), 0.5);
+}
+
+#[test]
+fn timeline_follows_varied_durations_and_loops() {
+    let script = Script::parse(
+        r#"
+        [[scene]]
+        text = "a"
+        color = [0.0, 0.0, 0.0]
+        duration = 1.0
+
+        [[scene]]
+        text = "b"
+        color = [0.0, 0.0, 0.0]
+        duration = 2.5
+        transition = "cut"
+        easing = "linear"
+        glow = [0.0, 1.0, 0.0]
+        "#,
+    )
+    .unwrap();
+
+    let timeline = script.timeline();
+    let at = |time| timeline.at(time).map(|a| (*a.item, a.local));
+    assert_eq!(at(0.0), Some((0, 0.0)));
+    assert_eq!(at(0.5), Some((0, 0.5)));
+    assert_eq!(at(1.5), Some((1, 0.5)));
+    assert_eq!(at(4.0), Some((0, 0.5)));
+    assert_eq!(script.next(1), 0);
+    assert_eq!(script.scenes[1].morph_progress(2.0), 0.0);
+    assert_eq!(script.scenes[1].glow(), [0.0, 1.0, 0.0]);
+}
+
+#[test]
+fn rejects_bad_scripts() {
+    assert!(matches!(Script::parse(""), Err(ScriptError::NoScenes)));
+    assert!(matches!(
+        Script::parse("[[scene]]\ntext = \"a\"\ncolor = [0.0, 0.0, 0.0]\nduration = 0.0\n"),
+        Err(ScriptError::BadDuration { scene: 0, .. })
+    ));
+    assert!(matches!(
+        Script::parse("[[scene]]\ntext = \"a\"\ncolor = [0.0, 0.0, 0.0]\nspeed = 2.0\n"),
+        Err(ScriptError::Parse(_))
+    ));
+    assert!(matches!(
+        Script::parse("[[scene]]\ntext = \"a\"\ncolor = [0.0, 0.0, 0.0]\neasing = \"wobbly\"\n"),
+        Err(ScriptError::Parse(_))
+    ));
+    assert!(matches!(
+        Script::parse(
+            "[[scene]]\ntext = \"a\"\ncolor = [0.0, 0.0, 0.0]\n[[scene]]\ntext = \" \"\ncolor = [0.0, 0.0, 0.0]\n"
+        ),
+        Err(ScriptError::EmptyText { scene: 1 })
+    ));
+    assert!(matches!(
+        Script::load("/nonexistent/reel.toml"),
+        Err(ScriptError::Io(_))
+    ));
+}
+
+#[test]
+fn scenes_name_any_easing() {
+    let script = Script::parse(
+        r#"
+        [[scene]]
+        text = "a"
+        color = [0.0, 0.0, 0.0]
+        easing = "back-out"
+
+        [[scene]]
+        text = "b"
+        color = [0.0, 0.0, 0.0]
+        easing = "cubic-bezier(0.4, 0, 0.2, 1)"
+
+        [[scene]]
+        text = "c"
+        color = [0.0, 0.0, 0.0]
+        easing = "steps(4)"
+        "#,
+    )
+    .unwrap();
+    let easings: Vec<Easing> = script.scenes.iter().map(|s| s.easing).collect();
+    assert_eq!(
+        easings,
+        [
+            Easing::Out(Curve::Back),
+            Easing::CubicBezier(0.4, 0.0, 0.2, 1.0),
+            Easing::Steps(4),
+        ]
+    );
+}
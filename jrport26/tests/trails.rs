@@ -0,0 +1,95 @@
+mod common;
+
+use commons::physics::Particle;
+use jrport26::particles::GpuParticles;
+use jrport26::reelone::ReelOne;
+use jrport26::trails::{TrailSettings, Trails};
+
+/// Renders one frame of trails, with or without the particles in it.
+fn trail_frame(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    trails: &mut Trails,
+    gpu: &GpuParticles,
+    dt: f32,
+    particles: bool,
+) {
+    let mut encoder = device.create_command_encoder(&Default::default());
+    trails.render(&mut encoder, queue, dt, |rpass| {
+        if particles {
+            gpu.draw(rpass)
+        }
+    });
+    queue.submit(Some(encoder.finish()));
+}
+
+#[test]
+#[ignore = "needs a wgpu adapter"]
+fn trails_fade_by_half_life() {
+    let frame = common::linear_frame(16);
+    let (device, queue) = (frame.device(), frame.queue());
+
+    let mut gpu = GpuParticles::new(device, Trails::FORMAT, &[Particle::at(8.0, 8.0)]);
+    gpu.style.radius = 4.0;
+    gpu.set_view(queue, 16.0, 16.0);
+    let mut trails = Trails::new(device, frame.format(), 16, 16);
+    trails.settings = TrailSettings {
+        half_life: 0.1,
+        ..Default::default()
+    };
+    let mut red = |dt: f32, particles: bool| {
+        trail_frame(device, queue, &mut trails, &gpu, dt, particles);
+        let pixels = common::render_pass(&frame, |rpass| trails.composite(rpass));
+        pixels[(7 * 16 + 7) * 4] as i32
+    };
+
+    assert_eq!(red(0.1, true), 255);
+    let faded = red(0.1, false);
+    assert!((faded - 128).abs() <= 1, "{faded}");
+    let faded = red(0.2, false);
+    assert!((faded - 32).abs() <= 1, "{faded}");
+}
+
+#[test]
+#[ignore = "needs a wgpu adapter"]
+fn trails_shift_hue() {
+    let frame = common::linear_frame(16);
+    let (device, queue) = (frame.device(), frame.queue());
+
+    let mut gpu = GpuParticles::new(device, Trails::FORMAT, &[Particle::at(8.0, 8.0)]);
+    gpu.style.radius = 4.0;
+    gpu.style.tint = [1.0, 0.0, 0.0, 1.0];
+    gpu.set_view(queue, 16.0, 16.0);
+    let mut trails = Trails::new(device, frame.format(), 16, 16);
+    // A third of a turn per frame moves red to green.
+    trails.settings = TrailSettings {
+        half_life: 1e6,
+        hue_shift: std::f32::consts::TAU / 3.0,
+        ..Default::default()
+    };
+    trail_frame(device, queue, &mut trails, &gpu, 1.0, true);
+    trail_frame(device, queue, &mut trails, &gpu, 1.0, false);
+    let pixels = common::render_pass(&frame, |rpass| trails.composite(rpass));
+    let at = &pixels[(7 * 16 + 7) * 4..(7 * 16 + 7) * 4 + 3];
+    assert!(at[0] <= 1 && at[1] >= 254 && at[2] <= 1, "{at:?}");
+}
+
+#[test]
+#[ignore = "needs a wgpu adapter"]
+fn trails_stay_bounded_at_high_frame_rates() {
+    let frame = common::linear_frame(64);
+    let (device, queue) = (frame.device(), frame.queue());
+
+    let mut gpu = GpuParticles::new(device, Trails::FORMAT, &[Particle::at(32.0, 32.0)]);
+    gpu.style.radius = 4.0;
+    gpu.set_view(queue, 64.0, 64.0);
+    let mut trails = Trails::new(device, frame.format(), 64, 64);
+    trails.settings = ReelOne::TRAILS;
+    // A second of particles then a second without them, at 144 fps.
+    for n in 0..288 {
+        trail_frame(device, queue, &mut trails, &gpu, 1.0 / 144.0, n < 144);
+    }
+    let pixels = common::render_pass(&frame, |rpass| trails.composite(rpass));
+    let brightest = pixels.chunks(4).flat_map(|px| &px[..3]).max().unwrap();
+    assert!(*brightest < 16, "trails grew to {brightest}");
+}